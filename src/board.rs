@@ -1,12 +1,19 @@
 use crate::{Color, Dir, Kind, Move, MoveKind, Piece, Square};
 use druid::{Data, Lens};
-use log::{info, warn};
-use std::ops::{Index, IndexMut};
+use log::info;
+use std::ops::Index;
 
+mod bitboard;
+use bitboard::*;
 mod casteling;
 use casteling::*;
 mod captures;
-use captures::*;
+pub use captures::*;
+mod chess960;
+pub use chess960::*;
+mod fen;
+pub use fen::*;
+mod zobrist;
 
 // https://en.wikipedia.org/wiki/Chess#Setup
 // https://en.wikipedia.org/wiki/Rules_of_chess
@@ -17,13 +24,31 @@ use captures::*;
 /// square board of eight rows (called ranks) and eight columns (called files).
 pub struct Board {
     pieces: [[Option<Piece>; 8]; 8],
+    // the same pieces as sets of squares, for move generation and attacks
+    bitboards: Bitboards,
     pub active: Color,
     pub castling: Castling,
     pub en_passant: Option<Square>,
+    /// number of halfmoves since the last capture or pawn move
     pub halfmove_clock: u32,
+    /// number of the full move, starting at 1 and incremented after black's move
     pub fullmove_number: u32,
 
     pub captures: Captures,
+
+    zobrist: u64,
+}
+
+/// the state of the board that a move destroys, returned by `Board::apply` and consumed by `Board::unapply`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Undo {
+    pub mv: Move,
+    pub captured: Option<Piece>,
+    castling: Castling,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+    zobrist: u64,
 }
 
 impl Index<Square> for Board {
@@ -34,42 +59,22 @@ impl Index<Square> for Board {
     }
 }
 
-impl IndexMut<Square> for Board {
-    fn index_mut(&mut self, square: Square) -> &mut Self::Output {
-        &mut self.pieces[square.rank as usize][square.file as usize]
-    }
-}
-
 impl Board {
     pub fn default() -> Self {
-        Board {
-            pieces: Board::parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").expect("syntax error in initial board"),
+        let pieces = Board::parse_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR", 0).expect("syntax error in initial board");
+        let mut board = Board {
+            pieces,
+            bitboards: Bitboards::new(&pieces),
             active: Color::White,
             castling: Default::default(),
             en_passant: None,
             halfmove_clock: 0,
-            fullmove_number: 0,
+            fullmove_number: 1,
             captures: Default::default(),
-        }
-    }
-
-    /// create board from Forsyth-Edwards Notation
-    /// https://en.wikipedia.org/wiki/Forsyth%E2%80%93Edwards_Notation
-    // Piece placement (from White's perspective). Each rank is described, starting with rank 8 and ending with rank 1; within each rank, the contents of each square are described from file "a" through file "h". Following the Standard Algebraic Notation (SAN), each piece is identified by a single letter taken from the standard English names (pawn = "P", knight = "N", bishop = "B", rook = "R", queen = "Q" and king = "K"). White pieces are designated using upper-case letters ("PNBRQK") while black pieces use lowercase ("pnbrqk"). Empty squares are noted using digits 1 through 8 (the number of empty squares), and "/" separates ranks.
-    // Active color. "w" means White moves next, "b" means Black moves next.
-    // Castling availability. If neither side can castle, this is "-". Otherwise, this has one or more letters: "K" (White can castle kingside), "Q" (White can castle queenside), "k" (Black can castle kingside), and/or "q" (Black can castle queenside). A move that temporarily prevents castling does not negate this notation.
-    // En passant target square in algebraic notation. If there's no en passant target square, this is "-". If a pawn has just made a two-square move, this is the position "behind" the pawn. This is recorded regardless of whether there is a pawn in position to make an en passant capture.[6]
-    // Halfmove clock: The number of halfmoves since the last capture or pawn advance, used for the fifty-move rule.[7]
-    // Fullmove number: The number of the full move. It starts at 1, and is incremented after Black's move.
-    pub fn from_fen(str: &str) -> Option<Self> {
-        let mut board = Board::default();
-
-        let mut sections = str.split_ascii_whitespace();
-        board.pieces = Board::parse_fen(sections.next()?)?;
-        board.active = Board::parse_active(sections.next()?)?;
-        board.castling = Castling::from_fen(sections.next()?)?;
-
-        Some(board)
+            zobrist: 0,
+        };
+        board.rehash();
+        board
     }
 
     // square board of eight rows (called ranks) and eight columns (called files).
@@ -85,33 +90,13 @@ impl Board {
         self.pieces[square.rank as usize][square.file as usize]
     }
 
-    fn parse_fen(str: &str) -> Option<[[Option<Piece>; 8]; 8]> {
-        let mut pieces = [[None; 8]; 8];
-        let mut i = 0;
-        let mut j = 0;
-        for c in str.chars() {
-            match c {
-                'p' | 'r' | 'n' | 'b' | 'q' | 'k' | 'P' | 'R' | 'N' | 'B' | 'Q' | 'K' => {
-                    pieces[j][i] = Piece::from_char(c).ok();
-                    i += 1;
-                }
-                '/' => {
-                    if i != 8 {
-                        warn!("syntax error in {}. expected '/' at {}/{} ", str, j, i)
-                    }
-                    j += 1;
-                    i = 0;
-                }
-                '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' => {
-                    let v = c as usize - '0' as usize;
-                    i += v;
-                }
-                _ => {
-                    return None;
-                }
-            }
-        }
-        Some(pieces)
+    /// put a piece on (or remove it from) a square, e.g. to set up a position.
+    /// the bitboards and the hash are kept up to date, castling rights and en passant are left as they are.
+    pub fn set_piece(&mut self, square: Square, piece: Option<Piece>) {
+        // whether en passant counts for the hash depends on the pawns next to the target
+        self.zobrist ^= self.en_passant_key();
+        self.set(square, piece);
+        self.zobrist ^= self.en_passant_key();
     }
 
     fn update_castling_elegibility(&mut self, mv: &Move) {
@@ -120,10 +105,20 @@ impl Board {
             self.castling[(self.active, Side::Queen)] = false;
             info!("disabling casteling for {:?}", self.active);
         }
-        if mv.piece.kind == Kind::Rook {
-            let side = if mv.origin.file < 4 { Side::Queen } else { Side::King };
-            self.castling[(self.active, side)] = false;
-            info!("disabling casteling for {:?} on {:?} side", self.active, side);
+        // moving a rook from its initial square or capturing it there
+        // disables castling on that side
+        for (color, square) in [(self.active, mv.origin), (-self.active, mv.target)] {
+            let home_rank = if color == Color::White { 7 } else { 0 };
+            if square.rank != home_rank {
+                continue;
+            }
+            for side in [Side::King, Side::Queen] {
+                if !self.castling[(color, side)] || self.castling.rook_file(color, side) != square.file {
+                    continue;
+                }
+                self.castling[(color, side)] = false;
+                info!("disabling casteling for {:?} on {:?} side", color, side);
+            }
         }
     }
 
@@ -156,63 +151,146 @@ impl Board {
         self.en_passant = it;
     }
 
-    pub fn apply(&mut self, mv: &Move) {
+    /// make the move and return the information needed to take it back with `unapply`
+    pub fn apply(&mut self, mv: &Move) -> Undo {
         assert_eq!(self.active, mv.piece.color);
 
         let opponent = -self.active;
         let Move { piece, origin, target, kind } = *mv;
+        let undo = Undo {
+            mv: *mv,
+            captured: mv.captured().map(|kind| Piece::new(opponent, kind)),
+            castling: self.castling,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            zobrist: self.zobrist,
+        };
+        // the parts of the hash that don't depend on single pieces are replaced as a whole
+        self.zobrist ^= zobrist::active_key(self.active) ^ zobrist::castling_key(&self.castling) ^ self.en_passant_key();
+
         match kind {
             MoveKind::Move() => {
-                self[origin] = None;
-                self[target] = Some(piece);
+                self.set(origin, None);
+                self.set(target, Some(piece));
             }
             MoveKind::Take(_kind) => {
                 assert_eq!(self[target], Some(Piece::new(opponent, _kind)));
                 info!("captured {:?}", self[target]);
 
-                self[origin] = None;
-                self[target] = Some(piece);
+                self.set(origin, None);
+                self.set(target, Some(piece));
             }
             MoveKind::EnPassant() => {
                 let passed = Square::new(target.file, origin.rank);
                 assert_eq!(self[passed], Some(Piece::new(opponent, Kind::Pawn)));
                 info!("captured en passant {:?}", self[passed]);
 
-                self[passed] = None;
-                self[origin] = None;
-                self[target] = Some(piece);
+                self.set(passed, None);
+                self.set(origin, None);
+                self.set(target, Some(piece));
             }
             MoveKind::Castle(rook_origin, rook_target) => {
                 assert_eq!(self[origin], Some(Piece::new(self.active, Kind::King)));
                 assert_eq!(self[rook_origin], Some(Piece::new(self.active, Kind::Rook)));
 
-                self[rook_target] = self[rook_origin];
-                self[rook_origin] = None;
-                self[origin] = None;
-                self[target] = Some(piece);
+                // in Chess960 king and rook can end up on each other's square
+                self.set(rook_origin, None);
+                self.set(origin, None);
+                self.set(rook_target, Some(Piece::new(self.active, Kind::Rook)));
+                self.set(target, Some(piece));
+            }
+            MoveKind::Promote(promotion) => {
+                assert_eq!(self[target], None);
+
+                self.set(origin, None);
+                self.set(target, Some(Piece::new(self.active, promotion)));
+            }
+            MoveKind::PromoteTake(_kind, promotion) => {
+                assert_eq!(self[target], Some(Piece::new(opponent, _kind)));
+                info!("captured {:?}", self[target]);
+
+                self.set(origin, None);
+                self.set(target, Some(Piece::new(self.active, promotion)));
             }
         }
 
         self.update_en_passant_elegibility(mv);
         self.update_castling_elegibility(mv);
-        self.update_captures(mv.kind);
+        self.update_captures(mv);
 
-        self.halfmove_clock += 1;
+        // https://en.wikipedia.org/wiki/Fifty-move_rule
+        let irreversible = piece.kind == Kind::Pawn || undo.captured.is_some();
+        self.halfmove_clock = if irreversible { 0 } else { self.halfmove_clock + 1 };
         self.fullmove_number += if mv.piece.color == Color::Black { 1 } else { 0 };
         self.active = -self.active;
+        self.zobrist ^= zobrist::active_key(self.active) ^ zobrist::castling_key(&self.castling) ^ self.en_passant_key();
+        undo
     }
 
-    fn update_captures(&mut self, kind: MoveKind) {
-        let opponent = -self.active;
+    /// take back the move that returned the given `Undo`, restoring the previous position exactly.
+    /// moves have to be taken back in the reverse order they were applied.
+    pub fn unapply(&mut self, undo: Undo) {
+        let Move { piece, origin, target, kind } = undo.mv;
+        assert_eq!(-self.active, piece.color);
 
+        self.active = piece.color;
         match kind {
-            MoveKind::Take(kind) => {
-                self.captures[opponent][kind] += 1;
+            MoveKind::Move() | MoveKind::Take(_) | MoveKind::Promote(_) | MoveKind::PromoteTake(_, _) => {
+                self.put(origin, Some(piece));
+                self.put(target, undo.captured);
             }
             MoveKind::EnPassant() => {
-                self.captures[opponent][Kind::Pawn] += 1;
+                let passed = Square::new(target.file, origin.rank);
+                self.put(passed, undo.captured);
+                self.put(origin, Some(piece));
+                self.put(target, None);
             }
-            _ => {}
+            MoveKind::Castle(rook_origin, rook_target) => {
+                self.put(target, None);
+                self.put(rook_target, None);
+                self.put(rook_origin, Some(Piece::new(piece.color, Kind::Rook)));
+                self.put(origin, Some(piece));
+            }
+        }
+
+        if let Some(captured) = undo.captured {
+            self.captures[captured.color][captured.kind] -= 1;
+        }
+        self.castling = undo.castling;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.fullmove_number = undo.fullmove_number;
+        self.zobrist = undo.zobrist;
+    }
+
+    /// put a piece on (or remove it from) a square and keep the hash up to date
+    fn set(&mut self, square: Square, piece: Option<Piece>) {
+        if let Some(old) = self[square] {
+            self.zobrist ^= zobrist::piece_key(square, old);
+        }
+        if let Some(new) = piece {
+            self.zobrist ^= zobrist::piece_key(square, new);
+        }
+        self.put(square, piece);
+    }
+
+    /// put a piece on (or remove it from) a square, keeping the bitboards in line but not the hash
+    fn put(&mut self, square: Square, piece: Option<Piece>) {
+        if let Some(old) = self[square] {
+            self.bitboards.toggle(square, old);
+        }
+        if let Some(new) = piece {
+            self.bitboards.toggle(square, new);
+        }
+        self.pieces[square.rank as usize][square.file as usize] = piece;
+    }
+
+    fn update_captures(&mut self, mv: &Move) {
+        let opponent = -self.active;
+
+        if let Some(kind) = mv.captured() {
+            self.captures[opponent][kind] += 1;
         }
     }
 
@@ -221,36 +299,85 @@ impl Board {
         if let Some(piece) = self[square] {
             match piece.kind {
                 Kind::King => self.get_king_moves(square, piece),
-                Kind::Queen => self.get_queen_moves(square, piece),
-                Kind::Rook => self.get_rook_moves(square, piece),
-                Kind::Bishop => self.get_bishop_moves(square, piece),
-                Kind::Knight => self.get_knight_moves(square, piece),
                 Kind::Pawn => self.get_pawn_moves(square, piece),
+                kind => self.get_piece_moves(square, piece, attacks(kind, square, self.bitboards.occupied())),
             }
         } else {
             vec![]
         }
     }
 
-    fn get_knight_moves(&self, square: Square, piece: Piece) -> Vec<Move> {
-        debug_assert!(piece.kind == Kind::Knight);
-        let opponent = -piece.color;
+    /// get the legal moves of the piece on given square, i.e. the valid moves
+    /// that don't leave the own king under attack
+    pub fn get_legal_moves(&self, square: Square) -> Vec<Move> {
+        self.get_valid_moves(square).into_iter().filter(|mv| self.keeps_king_safe(mv)).collect()
+    }
+
+    /// all legal moves of the active player
+    pub fn legal_moves(&self) -> Vec<Move> {
         let mut result = vec![];
+        for square in squares_of(self.bitboards.color(self.active)) {
+            result.extend(self.get_valid_moves(square).into_iter().filter(|mv| self.keeps_king_safe(mv)));
+        }
+        result
+    }
+
+    /// does the move keep the mover's king out of check
+    pub fn is_legal(&self, mv: &Move) -> bool {
+        self.keeps_king_safe(mv)
+    }
+
+    // the move is only made on the occupied squares, which is all it takes to find the attackers of the king
+    fn keeps_king_safe(&self, mv: &Move) -> bool {
+        let color = mv.piece.color;
+        let mut occupied = self.bitboards.occupied() & !bit(mv.origin);
+        // the opponent's pieces left after the move
+        let mut opponents = self.bitboards.color(-color) & !bit(mv.target);
+        match mv.kind {
+            MoveKind::EnPassant() => {
+                let passed = bit(Square::new(mv.target.file, mv.origin.rank));
+                occupied &= !passed;
+                opponents &= !passed;
+            }
+            MoveKind::Castle(rook_origin, rook_target) => occupied = occupied & !bit(rook_origin) | bit(rook_target),
+            _ => {}
+        }
+        occupied |= bit(mv.target);
+        let king = if mv.piece.kind == Kind::King { Some(mv.target) } else { self.king_square(color) };
+        match king {
+            Some(king) => self.attackers_among(king, -color, opponents, occupied) == 0,
+            None => true,
+        }
+    }
 
-        for target in square.offset_by(&dirs::DIRECTION_KNIGHT) {
+    /// count the pieces of the given color that are still on the board
+    pub fn material(&self, color: Color) -> PieceCounter {
+        let mut result = PieceCounter::default();
+        for &kind in &[Kind::King, Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight, Kind::Pawn] {
+            result[kind] = self.bitboards.pieces(color, kind).count_ones();
+        }
+        result
+    }
+
+    /// the square of the king of the given color
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        squares_of(self.bitboards.pieces(color, Kind::King)).next()
+    }
+
+    /// all 64 squares, rank by rank starting at a8
+    pub fn squares() -> impl Iterator<Item = Square> {
+        (0..8).flat_map(|rank| (0..8).map(move |file| Square::new(file, rank)))
+    }
+
+    /// the moves of a knight, bishop, rook or queen to the attacked squares that are not occupied by own pieces
+    fn get_piece_moves(&self, square: Square, piece: Piece, attacks: Bitboard) -> Vec<Move> {
+        let mut result = vec![];
+        for target in squares_of(attacks & !self.bitboards.color(piece.color)) {
             match self[target] {
-                Some(Piece { color, kind }) if color == opponent => {
-                    // take opponents piece
-                    // Piece, Square, Square, Kind),
-                    let mv = Move::new_take(piece, square, target, kind);
-                    result.push(mv);
-                }
-                None => {
-                    // move to empty square
-                    let mv = Move::new_move(piece, square, target);
-                    result.push(mv);
-                }
-                _ => {}
+                // take opponents piece
+                Some(Piece { kind, color: _ }) => result.push(Move::new_take(piece, square, target, kind)),
+                // move to empty square
+                None => result.push(Move::new_move(piece, square, target)),
             }
         }
         result
@@ -264,13 +391,18 @@ impl Board {
         let fwd = if piece.color == Color::White { -1 } else { 1 };
         let straight_fwd = Dir::new(0, fwd);
 
-        fn valid_squares(items: &[Square]) -> impl Iterator<Item = Square> + '_ {
-            items.iter().filter(|&item| item.valid()).copied()
-        }
+        // https://en.wikipedia.org/wiki/Chess#Promotion
+        let final_rank = if piece.color == Color::White { 0 } else { 7 };
+        const PROMOTIONS: [Kind; 4] = [Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight];
 
         // take diagonally or en-passant
-        for dest in valid_squares(&[origin + Dir(1, fwd), origin + Dir(-1, fwd)]) {
+        for dest in squares_of(pawn_attacks(piece.color, origin)) {
             match self[dest] {
+                Some(take) if take.color == opponent && dest.rank == final_rank => {
+                    for &promotion in &PROMOTIONS {
+                        result.push(Move::new_promote_take(piece, origin, dest, take.kind, promotion));
+                    }
+                }
                 Some(take) if take.color == opponent => {
                     result.push(Move::new_take(piece, origin, dest, take.kind));
                 }
@@ -283,79 +415,73 @@ impl Board {
         }
 
         let starting_rank = if piece.color == Color::White { 6 } else { 1 };
-        // initial move straight
+        // initial move straight, both squares have to be empty
         if origin.rank == starting_rank {
             let target = origin + straight_fwd * 2;
-            if target.valid() && self[target].is_none() {
+            if target.valid() && self[target].is_none() && self[origin + straight_fwd].is_none() {
                 result.push(Move::new_move(piece, origin, target));
             }
         }
-        // regular move straight
+        // regular move straight, promoting on the final rank
         let target = origin + straight_fwd;
         if target.valid() && self[target].is_none() {
-            result.push(Move::new_move(piece, origin, target));
+            if target.rank == final_rank {
+                for &promotion in &PROMOTIONS {
+                    result.push(Move::new_promote(piece, origin, target, promotion));
+                }
+            } else {
+                result.push(Move::new_move(piece, origin, target));
+            }
         }
 
-        // let final_rank = if piece.color == Color::White { 0 } else { 7 };
-        // for dest in valid(&[origin + Dir(fwd, 0)]) {
-        //     if self.board[dest] == None {
-        //         if dest.0 != end {
-        //             result.add(Move::Move(self.player, origin, Piece::Pawn, dest));
-        //         } else {
-        //             // https://en.wikipedia.org/wiki/Chess#Promotion
-        //             for promo in [Piece::Queen, Piece::Rook, Piece::Knight, Piece::Bishop] {
-        //                 result.add(Move::Promote(self.player, origin, dest, promo));
-        //             }
-        //         }
-        //     }
-        // }
-
-        result
-    }
-
-    fn get_queen_moves(&self, square: Square, piece: Piece) -> Vec<Move> {
-        debug_assert!(piece.kind == Kind::Queen);
-        // let opponent = -piece.color;
-        let mut result = vec![];
-
-        for &dir in &dirs::DIRECTION_BOTH {
-            self.add_moves_in_dir(&mut result, dir, piece, square, 7);
-        }
         result
     }
 
     fn get_king_moves(&self, square: Square, piece: Piece) -> Vec<Move> {
         debug_assert!(piece.kind == Kind::King);
-        let mut result = vec![];
-
-        for &dir in &dirs::DIRECTION_BOTH {
-            self.add_moves_in_dir(&mut result, dir, piece, square, 1);
-        }
+        let mut result = self.get_piece_moves(square, piece, king_attacks(square));
 
         // Castling is performed on the kingside or queenside with the rook on the same rank.[5]
         // Neither the king nor the chosen rook has previously moved.
         // There are no pieces between the king and the chosen rook.
         // One may not castle out of, through, or into check.
+        // In Chess960 king and rook end up on the same squares as in standard chess.
         if self.castling[(self.active, Side::King)] {
-            self.castle(piece, square.rank, 6, 7, 5).map(|mv| result.push(mv));
+            let rook_file = self.castling.rook_file(self.active, Side::King);
+            self.castle(piece, square, 6, rook_file, 5).map(|mv| result.push(mv));
         }
         if self.castling[(self.active, Side::Queen)] {
-            self.castle(piece, square.rank, 2, 0, 3).map(|mv| result.push(mv));
+            let rook_file = self.castling.rook_file(self.active, Side::Queen);
+            self.castle(piece, square, 2, rook_file, 3).map(|mv| result.push(mv));
         }
         result
     }
 
-    fn castle(&self, piece: Piece, rank: i32, king_dst_file: i32, rook_src_file: i32, rook_dst_file: i32) -> Option<Move> {
-        const KING_SRC_FILE: i32 = 4;
-        debug_assert_eq!((KING_SRC_FILE - king_dst_file).abs(), 2);
+    fn castle(&self, piece: Piece, k_src: Square, king_dst_file: i32, rook_src_file: i32, rook_dst_file: i32) -> Option<Move> {
         let opponent = -self.active;
+        let rank = k_src.rank;
+        // castling rights don't travel with a king that left its home rank
+        let home_rank = if self.active == Color::White { 7 } else { 0 };
+        if rank != home_rank {
+            return None;
+        }
 
-        let k_src = Square::new(KING_SRC_FILE, rank);
-        let k_thr = Square::new((king_dst_file + KING_SRC_FILE) / 2, rank);
         let k_dst = Square::new(king_dst_file, rank);
         let r_src = Square::new(rook_src_file, rank);
         let r_dst = Square::new(rook_dst_file, rank);
-        if !self.is_under_attack(k_src, opponent) && !self.is_under_attack(k_dst, opponent) && !self.is_under_attack(k_thr, opponent) {
+        if self[r_src] != Some(Piece::new(self.active, Kind::Rook)) {
+            return None;
+        }
+
+        // all squares king and rook pass or land on have to be empty, except for the king and rook themselves
+        let files = [k_src.file, k_dst.file, r_src.file, r_dst.file];
+        let (lo, hi) = (*files.iter().min().unwrap(), *files.iter().max().unwrap());
+        if (lo..=hi).map(|file| Square::new(file, rank)).any(|sq| sq != k_src && sq != r_src && self[sq].is_some()) {
+            return None;
+        }
+        // the king may not start on, pass or land on an attacked square
+        let (lo, hi) = (k_src.file.min(k_dst.file), k_src.file.max(k_dst.file));
+        if (lo..=hi).all(|file| !self.is_under_attack(Square::new(file, rank), opponent)) {
             let mc = Move::new_castle(piece, k_src, k_dst, r_src, r_dst);
             info!("casteling {:?} possible", mc);
             return Some(mc);
@@ -363,59 +489,32 @@ impl Board {
         None
     }
 
-    fn is_under_attack(&self, _square: Square, _by: Color) -> bool {
-        false
-    }
-
-    fn get_rook_moves(&self, square: Square, piece: Piece) -> Vec<Move> {
-        debug_assert!(piece.kind == Kind::Rook);
-        let mut result = vec![];
-
-        for &dir in &dirs::DIRECTION_RECT {
-            self.add_moves_in_dir(&mut result, dir, piece, square, 7);
-        }
-        result
+    /// is the given square attacked by any piece of color `by`
+    pub fn is_under_attack(&self, square: Square, by: Color) -> bool {
+        self.attackers(square, by) != 0
     }
 
-    fn get_bishop_moves(&self, square: Square, piece: Piece) -> Vec<Move> {
-        debug_assert!(piece.kind == Kind::Bishop);
-        let mut result = vec![];
-
-        for &dir in &dirs::DIRECTION_DIAG {
-            self.add_moves_in_dir(&mut result, dir, piece, square, 7);
-        }
-        result
+    /// the squares of all pieces of color `by` that attack the given square.
+    /// a square counts as attacked even if it is occupied by a piece of color `by` itself.
+    pub fn attackers_of(&self, square: Square, by: Color) -> Vec<Square> {
+        squares_of(self.attackers(square, by)).collect()
     }
 
-    fn add_moves_in_dir(&self, result: &mut Vec<Move>, dir: Dir, piece: Piece, start: Square, n: i32) {
-        let opponent = -piece.color;
-        for target in start.in_direction(dir, n) {
-            match self[target] {
-                // opponents piece -> take it and stop moving in this direction
-                Some(Piece { kind, color }) if color == opponent => {
-                    result.push(Move::new_take(piece, start, target, kind));
-                    break;
-                }
-                // own piece -> stop moving in this direction
-                Some(_) => {
-                    break;
-                }
-                // move to empty square and keep going
-                None => {
-                    result.push(Move::new_move(piece, start, target));
-                }
-            }
-        }
+    fn attackers(&self, square: Square, by: Color) -> Bitboard {
+        self.attackers_among(square, by, self.bitboards.color(by), self.bitboards.occupied())
     }
 
-    fn parse_active(co: &str) -> Option<Color> {
-        if co == "w" {
-            Some(Color::White)
-        } else if co == "b" {
-            Some(Color::Black)
-        } else {
-            None
-        }
+    /// the attackers of color `by` among the given squares,
+    /// with the lines of the sliding pieces blocked by the given occupied squares
+    fn attackers_among(&self, square: Square, by: Color, among: Bitboard, occupied: Bitboard) -> Bitboard {
+        let pieces = |kind| self.bitboards.kind(kind) & among;
+        // every piece but the pawn attacks the squares it is attacked from
+        (knight_attacks(square) & pieces(Kind::Knight))
+            | (king_attacks(square) & pieces(Kind::King))
+            | (rook_attacks(square, occupied) & (pieces(Kind::Rook) | pieces(Kind::Queen)))
+            | (bishop_attacks(square, occupied) & (pieces(Kind::Bishop) | pieces(Kind::Queen)))
+            // pawns of `by` attack the square from where a pawn of the other color on it would attack
+            | (pawn_attacks(-by, square) & pieces(Kind::Pawn))
     }
 }
 
@@ -0,0 +1,166 @@
+use druid::Data;
+
+use crate::{Color, Kind, Piece, Square};
+
+// https://www.chessprogramming.org/Bitboards
+// https://www.chessprogramming.org/Classical_Approach
+
+/// a set of squares, one bit per square. bit `8 * rank + file` stands for the square,
+/// so a8 is bit 0 and h1 is bit 63.
+pub type Bitboard = u64;
+
+fn index(square: Square) -> usize {
+    (square.rank * 8 + square.file) as usize
+}
+
+/// the set with only the given square
+pub fn bit(square: Square) -> Bitboard {
+    1 << index(square)
+}
+
+/// the squares of the set, in the same order as `Board::squares`
+pub fn squares_of(mut bitboard: Bitboard) -> impl Iterator<Item = Square> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let index = bitboard.trailing_zeros() as i32;
+        bitboard &= bitboard - 1;
+        Some(Square::new(index % 8, index / 8))
+    })
+}
+
+/// the squares occupied by each color and by each kind of piece
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Data)]
+pub struct Bitboards {
+    colors: [Bitboard; 2],
+    kinds: [Bitboard; 6],
+}
+
+impl Bitboards {
+    pub fn new(pieces: &[[Option<Piece>; 8]; 8]) -> Self {
+        let mut result = Bitboards { colors: [0; 2], kinds: [0; 6] };
+        for (rank, row) in pieces.iter().enumerate() {
+            for (file, piece) in row.iter().enumerate() {
+                if let Some(piece) = piece {
+                    result.toggle(Square::new(file as i32, rank as i32), *piece);
+                }
+            }
+        }
+        result
+    }
+
+    /// add the piece if it isn't on the square, remove it if it is
+    pub fn toggle(&mut self, square: Square, piece: Piece) {
+        self.colors[piece.color as usize] ^= bit(square);
+        self.kinds[piece.kind as usize] ^= bit(square);
+    }
+
+    pub fn occupied(&self) -> Bitboard {
+        self.colors[0] | self.colors[1]
+    }
+
+    pub fn color(&self, color: Color) -> Bitboard {
+        self.colors[color as usize]
+    }
+
+    pub fn kind(&self, kind: Kind) -> Bitboard {
+        self.kinds[kind as usize]
+    }
+
+    pub fn pieces(&self, color: Color, kind: Kind) -> Bitboard {
+        self.colors[color as usize] & self.kinds[kind as usize]
+    }
+}
+
+/// (file, rank) steps of the sliding pieces, rook directions first
+const DIRECTIONS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, 1), (1, -1), (-1, -1)];
+const KNIGHT: [(i32, i32); 8] = [(2, 1), (1, 2), (-1, 2), (-2, 1), (-2, -1), (-1, -2), (1, -2), (2, -1)];
+
+struct Tables {
+    knight: [Bitboard; 64],
+    king: [Bitboard; 64],
+    pawn: [[Bitboard; 64]; 2],
+    /// the squares from a square to the edge of the board in each of the `DIRECTIONS`
+    rays: [[Bitboard; 64]; 8],
+}
+
+lazy_static! {
+    static ref TABLES: Tables = {
+        let mut tables = Tables {
+            knight: [0; 64],
+            king: [0; 64],
+            pawn: [[0; 64]; 2],
+            rays: [[0; 64]; 8],
+        };
+        let steps = |square: Square, steps: &[(i32, i32)]| {
+            steps
+                .iter()
+                .map(|&(file, rank)| Square::new(square.file + file, square.rank + rank))
+                .filter(Square::valid)
+                .fold(0, |bitboard, target| bitboard | bit(target))
+        };
+        for rank in 0..8 {
+            for file in 0..8 {
+                let square = Square::new(file, rank);
+                let i = index(square);
+                tables.knight[i] = steps(square, &KNIGHT);
+                tables.king[i] = steps(square, &DIRECTIONS);
+                // white pawns move towards rank 0
+                tables.pawn[Color::White as usize][i] = steps(square, &[(-1, -1), (1, -1)]);
+                tables.pawn[Color::Black as usize][i] = steps(square, &[(-1, 1), (1, 1)]);
+                for (d, &(df, dr)) in DIRECTIONS.iter().enumerate() {
+                    let ray = (1..8).map(|n| Square::new(file + df * n, rank + dr * n)).take_while(Square::valid);
+                    tables.rays[d][i] = ray.fold(0, |bitboard, target| bitboard | bit(target));
+                }
+            }
+        }
+        tables
+    };
+}
+
+pub fn knight_attacks(square: Square) -> Bitboard {
+    TABLES.knight[index(square)]
+}
+
+pub fn king_attacks(square: Square) -> Bitboard {
+    TABLES.king[index(square)]
+}
+
+/// the squares a pawn of the given color on the square attacks
+pub fn pawn_attacks(color: Color, square: Square) -> Bitboard {
+    TABLES.pawn[color as usize][index(square)]
+}
+
+/// the squares along the ray up to and including the first occupied square
+fn ray_attacks(direction: usize, square: Square, occupied: Bitboard) -> Bitboard {
+    let ray = TABLES.rays[direction][index(square)];
+    let blockers = ray & occupied;
+    if blockers == 0 {
+        return ray;
+    }
+    let (df, dr) = DIRECTIONS[direction];
+    // rays towards higher bits hit the lowest blocker first, the others the highest
+    let first = if dr * 8 + df > 0 { blockers.trailing_zeros() } else { 63 - blockers.leading_zeros() };
+    ray ^ TABLES.rays[direction][first as usize]
+}
+
+pub fn rook_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    (0..4).fold(0, |attacks, d| attacks | ray_attacks(d, square, occupied))
+}
+
+pub fn bishop_attacks(square: Square, occupied: Bitboard) -> Bitboard {
+    (4..8).fold(0, |attacks, d| attacks | ray_attacks(d, square, occupied))
+}
+
+/// the squares a piece of the given kind on the square attacks, pawns excluded
+pub fn attacks(kind: Kind, square: Square, occupied: Bitboard) -> Bitboard {
+    match kind {
+        Kind::King => king_attacks(square),
+        Kind::Queen => rook_attacks(square, occupied) | bishop_attacks(square, occupied),
+        Kind::Rook => rook_attacks(square, occupied),
+        Kind::Bishop => bishop_attacks(square, occupied),
+        Kind::Knight => knight_attacks(square),
+        Kind::Pawn => unreachable!("pawn attacks depend on the color"),
+    }
+}
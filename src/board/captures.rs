@@ -42,6 +42,23 @@ impl Default for PieceCounter {
     }
 }
 
+impl PieceCounter {
+    /// the number of pieces of the given kinds
+    pub fn count(&self, kinds: &[Kind]) -> u32 {
+        kinds.iter().map(|&kind| self[kind]).sum()
+    }
+
+    /// the number of bishops and knights
+    pub fn minor_pieces(&self) -> u32 {
+        self.count(&[Kind::Bishop, Kind::Knight])
+    }
+
+    /// the number of rooks and queens
+    pub fn major_pieces(&self) -> u32 {
+        self.count(&[Kind::Rook, Kind::Queen])
+    }
+}
+
 impl Index<Kind> for PieceCounter {
     type Output = u32;
 
@@ -3,33 +3,64 @@ use std::ops::{Index, IndexMut};
 
 use druid::Data;
 
-use crate::Color;
+use crate::{Board, Color, Kind, Piece, Square};
 
+// https://en.wikipedia.org/wiki/Fischer_random_chess#Castling_rules
+
+/// the castling rights and the files of the rooks that castle.
+/// in standard chess the rooks start on the a and h file, in Chess960 they can start on any file.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Data)]
 pub struct Castling {
     flags: [[bool; 2]; 2],
+    rook_files: [[i32; 2]; 2],
 }
 
 impl Castling {
     fn none() -> Self {
-        Castling { flags: [[false; 2]; 2] }
+        Castling {
+            flags: [[false; 2]; 2],
+            rook_files: [[7, 0]; 2],
+        }
+    }
+
+    /// the file the rook castling on this side started on
+    pub(crate) fn rook_file(&self, color: Color, side: Side) -> i32 {
+        self.rook_files[color as usize][side as usize]
     }
 
-    pub(crate) fn from_fen(text: &str) -> Option<Self> {
+    /// the castling field of a FEN. besides "KQkq" this accepts Shredder-FEN, which gives the files
+    /// of the rooks ("HAha"), and X-FEN, where "K" and "Q" stand for the outermost rook on that side.
+    /// the pieces have to be placed on the board already.
+    // https://en.wikipedia.org/wiki/X-FEN
+    pub(crate) fn from_fen(text: &str, board: &Board) -> Option<Self> {
         let mut result = Castling::none();
         if text == "-" {
             return Some(result);
         }
         for ch in text.chars() {
-            match ch {
-                'K' => result[(Color::White, Side::King)] = true,
-                'Q' => result[(Color::White, Side::Queen)] = true,
-                'k' => result[(Color::Black, Side::King)] = true,
-                'q' => result[(Color::Black, Side::Queen)] = true,
+            let color = if ch.is_ascii_uppercase() { Color::White } else { Color::Black };
+            let rank = if color == Color::White { 7 } else { 0 };
+            let rook = Some(Piece::new(color, Kind::Rook));
+            // without a king on the home rank the rights are kept, but `Board::castle` never uses them
+            let king_file = (0..8).find(|&file| board[Square::new(file, rank)] == Some(Piece::new(color, Kind::King))).unwrap_or(4);
+            let (side, file) = match ch.to_ascii_lowercase() {
+                'k' => (Side::King, (king_file + 1..8).rev().find(|&file| board[Square::new(file, rank)] == rook).unwrap_or(7)),
+                'q' => (Side::Queen, (0..king_file).find(|&file| board[Square::new(file, rank)] == rook).unwrap_or(0)),
+                c @ 'a'..='h' => {
+                    let file = c as i32 - 'a' as i32;
+                    let side = match file {
+                        file if file > king_file => Side::King,
+                        file if file < king_file => Side::Queen,
+                        _ => return None,
+                    };
+                    (side, file)
+                }
                 _ => {
                     return None;
                 }
-            }
+            };
+            result[(color, side)] = true;
+            result.rook_files[color as usize][side as usize] = file;
         }
         Some(result)
     }
@@ -37,28 +68,27 @@ impl Castling {
 
 impl Default for Castling {
     fn default() -> Castling {
-        Self { flags: [[true; 2]; 2] }
+        Self {
+            flags: [[true; 2]; 2],
+            rook_files: [[7, 0]; 2],
+        }
     }
 }
 
+/// "KQkq" for rooks starting in the corners, otherwise the file of the rook as in Shredder-FEN
 impl Display for Castling {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut some = false;
-        if self[(Color::White, Side::King)] {
-            write!(f, "K")?;
-            some = true;
-        }
-        if self[(Color::White, Side::Queen)] {
-            write!(f, "Q")?;
-            some = true;
-        }
-        if self[(Color::Black, Side::King)] {
-            write!(f, "k")?;
-            some = true;
-        }
-        if self[(Color::Black, Side::Queen)] {
-            write!(f, "q")?;
-            some = true;
+        for &color in &[Color::White, Color::Black] {
+            for &(side, corner, letter) in &[(Side::King, 7, 'k'), (Side::Queen, 0, 'q')] {
+                if !self[(color, side)] {
+                    continue;
+                }
+                let file = self.rook_file(color, side);
+                let ch = if file == corner { letter } else { (b'a' + file as u8) as char };
+                write!(f, "{}", if color == Color::White { ch.to_ascii_uppercase() } else { ch })?;
+                some = true;
+            }
         }
         if !some {
             write!(f, "-")?;
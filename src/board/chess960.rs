@@ -0,0 +1,44 @@
+use crate::Board;
+
+// https://en.wikipedia.org/wiki/Fischer_random_chess_numbering_scheme
+
+/// the number of Chess960 start positions
+pub const CHESS960_POSITIONS: u32 = 960;
+
+impl Board {
+    /// the Chess960 start position with the given number from 0 to 959, using Scharnagl's numbering.
+    /// number 518 is the start position of standard chess.
+    pub fn chess960(index: u32) -> Board {
+        assert!(index < CHESS960_POSITIONS, "there is no Chess960 position {}", index);
+        let mut rank = [None; 8];
+        let mut n = index as usize;
+
+        // the bishops on a light (b, d, f, h) and a dark (a, c, e, g) square
+        rank[n % 4 * 2 + 1] = Some('b');
+        n /= 4;
+        rank[n % 4 * 2] = Some('b');
+        n /= 4;
+
+        // the queen and the knights on the remaining squares
+        let mut place = |nth: usize, piece: char| {
+            let file = (0..8).filter(|&file| rank[file].is_none()).nth(nth).expect("square left");
+            rank[file] = Some(piece);
+        };
+        place(n % 6, 'q');
+        n /= 6;
+        const KNIGHTS: [(usize, usize); 10] = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (1, 3), (1, 4), (2, 3), (2, 4), (3, 4)];
+        let (first, second) = KNIGHTS[n];
+        // placing the first knight shifts the squares after it
+        place(second, 'n');
+        place(first, 'n');
+
+        // rook, king and rook on the three squares left
+        for piece in ['r', 'k', 'r'] {
+            place(0, piece);
+        }
+
+        let black: String = rank.iter().map(|piece| piece.expect("all squares filled")).collect();
+        let fen = format!("{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1", black, black.to_ascii_uppercase());
+        Board::from_fen(&fen).expect("valid Chess960 position")
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt::Display;
+
+use crate::{Board, Color, Piece, Square};
+
+use super::{Bitboards, Castling};
+
+/// the six space separated fields of a FEN record
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FenField {
+    Placement,
+    ActiveColor,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+/// error returned by `Board::from_fen`.
+/// positions are character offsets into the FEN string.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError {
+    /// a required field is missing
+    MissingField(FenField),
+    /// there is more text after the fullmove number
+    TrailingInput { position: usize },
+    /// a character in the piece placement that is neither a piece, a number of empty squares nor '/'
+    InvalidPiece { position: usize, found: char },
+    /// a rank (8 = first rank in the string) doesn't describe exactly eight squares
+    InvalidRankLength { rank: i32, position: usize, squares: usize },
+    /// the piece placement doesn't describe exactly eight ranks
+    InvalidRankCount { position: usize, ranks: usize },
+    /// the text of a field other than the piece placement can't be parsed
+    InvalidField { field: FenField, position: usize, text: String },
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FenError::MissingField(field) => write!(f, "missing {:?} field", field),
+            FenError::TrailingInput { position } => write!(f, "unexpected input at {}", position),
+            FenError::InvalidPiece { position, found } => write!(f, "'{}' at {} is not a valid piece", found, position),
+            FenError::InvalidRankLength { rank, position, squares } => {
+                write!(f, "rank {} ending at {} has {} squares instead of 8", rank, position, squares)
+            }
+            FenError::InvalidRankCount { position, ranks } => write!(f, "placement ending at {} has {} ranks instead of 8", position, ranks),
+            FenError::InvalidField { field, position, text } => write!(f, "invalid {:?} field '{}' at {}", field, text, position),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Board {
+    /// create board from Forsyth-Edwards Notation
+    /// https://en.wikipedia.org/wiki/Forsyth%E2%80%93Edwards_Notation
+    // Piece placement (from White's perspective). Each rank is described, starting with rank 8 and ending with rank 1; within each rank, the contents of each square are described from file "a" through file "h". Following the Standard Algebraic Notation (SAN), each piece is identified by a single letter taken from the standard English names (pawn = "P", knight = "N", bishop = "B", rook = "R", queen = "Q" and king = "K"). White pieces are designated using upper-case letters ("PNBRQK") while black pieces use lowercase ("pnbrqk"). Empty squares are noted using digits 1 through 8 (the number of empty squares), and "/" separates ranks.
+    // Active color. "w" means White moves next, "b" means Black moves next.
+    // Castling availability. If neither side can castle, this is "-". Otherwise, this has one or more letters: "K" (White can castle kingside), "Q" (White can castle queenside), "k" (Black can castle kingside), and/or "q" (Black can castle queenside). A move that temporarily prevents castling does not negate this notation.
+    // For Chess960 the files of the castling rooks can be given instead, see `Castling::from_fen`.
+    // En passant target square in algebraic notation. If there's no en passant target square, this is "-". If a pawn has just made a two-square move, this is the position "behind" the pawn. This is recorded regardless of whether there is a pawn in position to make an en passant capture.[6]
+    // Halfmove clock: The number of halfmoves since the last capture or pawn advance, used for the fifty-move rule.[7]
+    // Fullmove number: The number of the full move. It starts at 1, and is incremented after Black's move.
+    //
+    // the last three fields may be omitted and then default to "- 0 1"
+    pub fn from_fen(str: &str) -> Result<Self, FenError> {
+        let mut board = Board::default();
+
+        let mut sections = fields(str);
+        let mut next = |field: FenField| sections.next().ok_or(FenError::MissingField(field));
+
+        let (pos, text) = next(FenField::Placement)?;
+        board.pieces = Board::parse_fen(text, pos)?;
+        board.bitboards = Bitboards::new(&board.pieces);
+
+        let (pos, text) = next(FenField::ActiveColor)?;
+        board.active = Board::parse_active(text).ok_or_else(|| invalid(FenField::ActiveColor, pos, text))?;
+
+        let (pos, text) = next(FenField::Castling)?;
+        board.castling = Castling::from_fen(text, &board).ok_or_else(|| invalid(FenField::Castling, pos, text))?;
+
+        board.en_passant = None;
+        if let Ok((pos, text)) = next(FenField::EnPassant) {
+            board.en_passant = Board::parse_en_passant(text, board.active).ok_or_else(|| invalid(FenField::EnPassant, pos, text))?;
+        }
+
+        board.halfmove_clock = 0;
+        if let Ok((pos, text)) = next(FenField::HalfmoveClock) {
+            board.halfmove_clock = text.parse().map_err(|_| invalid(FenField::HalfmoveClock, pos, text))?;
+        }
+
+        board.fullmove_number = 1;
+        if let Ok((pos, text)) = next(FenField::FullmoveNumber) {
+            match text.parse::<u32>() {
+                Ok(n) if n >= 1 => board.fullmove_number = n,
+                _ => return Err(invalid(FenField::FullmoveNumber, pos, text)),
+            }
+        }
+
+        if let Some((pos, _)) = sections.next() {
+            return Err(FenError::TrailingInput { position: pos });
+        }
+        board.rehash();
+        Ok(board)
+    }
+
+    /// write the board in Forsyth-Edwards Notation, the inverse of `from_fen`
+    pub fn to_fen(&self) -> String {
+        let mut result = String::new();
+        for (j, rank) in self.pieces.iter().enumerate() {
+            if j > 0 {
+                result.push('/');
+            }
+            let mut empty = 0;
+            for square in rank {
+                match square {
+                    Some(piece) => {
+                        if empty > 0 {
+                            result.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        result.push_str(&piece.to_string());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                result.push_str(&empty.to_string());
+            }
+        }
+        let active = if self.active == Color::White { "w" } else { "b" };
+        let en_passant = self.en_passant.map_or("-".to_string(), |sq| sq.to_string());
+        format!(
+            "{} {} {} {} {} {}",
+            result,
+            active,
+            self.castling,
+            en_passant,
+            self.halfmove_clock,
+            self.fullmove_number
+        )
+    }
+
+    /// parse the piece placement field, `offset` is the position of the field in the FEN string
+    pub(super) fn parse_fen(str: &str, offset: usize) -> Result<[[Option<Piece>; 8]; 8], FenError> {
+        let mut pieces = [[None; 8]; 8];
+        let mut i = 0;
+        let mut j = 0;
+        let end = offset + str.chars().count();
+        for (pos, c) in str.chars().enumerate().map(|(pos, c)| (pos + offset, c)) {
+            // squares after the eighth rank make a ninth rank, not a longer one
+            if j >= 8 && c != '/' {
+                return Err(FenError::InvalidRankCount { position: end, ranks: str.split('/').count() });
+            }
+            match c {
+                'p' | 'r' | 'n' | 'b' | 'q' | 'k' | 'P' | 'R' | 'N' | 'B' | 'Q' | 'K' => {
+                    if i >= 8 {
+                        return Err(FenError::InvalidRankLength {
+                            rank: 8 - j as i32,
+                            position: pos,
+                            squares: i + 1,
+                        });
+                    }
+                    pieces[j][i] = Piece::from_char(c).ok();
+                    i += 1;
+                }
+                // 9 is never right, but it is a rank that is too long rather than an unknown piece
+                '1'..='9' => {
+                    let v = c as usize - '0' as usize;
+                    i += v;
+                    if i > 8 {
+                        return Err(FenError::InvalidRankLength {
+                            rank: 8 - j as i32,
+                            position: pos,
+                            squares: i,
+                        });
+                    }
+                }
+                '/' => {
+                    if i != 8 {
+                        return Err(FenError::InvalidRankLength {
+                            rank: 8 - j as i32,
+                            position: pos,
+                            squares: i,
+                        });
+                    }
+                    j += 1;
+                    i = 0;
+                }
+                _ => {
+                    return Err(FenError::InvalidPiece { position: pos, found: c });
+                }
+            }
+        }
+        if j != 7 {
+            return Err(FenError::InvalidRankCount { position: end, ranks: j + 1 });
+        }
+        if i != 8 {
+            return Err(FenError::InvalidRankLength {
+                rank: 1,
+                position: end,
+                squares: i,
+            });
+        }
+        Ok(pieces)
+    }
+
+    fn parse_active(co: &str) -> Option<Color> {
+        if co == "w" {
+            Some(Color::White)
+        } else if co == "b" {
+            Some(Color::Black)
+        } else {
+            None
+        }
+    }
+
+    /// the en passant square has to be behind a pawn that just moved two squares,
+    /// i.e. on the 6th rank if white is to move and on the 3rd rank if black is to move
+    fn parse_en_passant(text: &str, active: Color) -> Option<Option<Square>> {
+        if text == "-" {
+            return Some(None);
+        }
+        let square = Square::from_an(text).ok()?;
+        let expected_rank = if active == Color::White { 2 } else { 5 };
+        if square.rank == expected_rank {
+            Some(Some(square))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_fen())
+    }
+}
+
+fn invalid(field: FenField, position: usize, text: &str) -> FenError {
+    FenError::InvalidField {
+        field,
+        position,
+        text: text.to_string(),
+    }
+}
+
+/// split the FEN string at whitespace, keeping the character position of each field
+fn fields(str: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut result = vec![];
+    let mut start = None;
+    for (pos, (byte, c)) in str.char_indices().enumerate() {
+        match (c.is_ascii_whitespace(), start) {
+            (false, None) => start = Some((pos, byte)),
+            (true, Some((first, begin))) => {
+                result.push((first, &str[begin..byte]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((first, begin)) = start {
+        result.push((first, &str[begin..]));
+    }
+    result.into_iter()
+}
@@ -0,0 +1,110 @@
+use crate::{Board, Color, Kind, Move, Piece, Square};
+
+use super::{Castling, Side};
+
+// https://www.chessprogramming.org/Zobrist_Hashing
+
+struct Keys {
+    pieces: [[u64; 64]; 12],
+    black_to_move: u64,
+    castling: [[u64; 2]; 2],
+    en_passant: [u64; 8],
+}
+
+lazy_static! {
+    static ref KEYS: Keys = {
+        // fixed seed so that hashes are the same in every run
+        let mut state = 0x5343_4841_4348_2121_u64;
+        let mut next = move || {
+            // https://en.wikipedia.org/wiki/Xorshift#xorshift*
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        };
+        let mut keys = Keys {
+            pieces: [[0; 64]; 12],
+            black_to_move: 0,
+            castling: [[0; 2]; 2],
+            en_passant: [0; 8],
+        };
+        keys.pieces.iter_mut().flatten().for_each(|key| *key = next());
+        keys.black_to_move = next();
+        keys.castling.iter_mut().flatten().for_each(|key| *key = next());
+        keys.en_passant.iter_mut().for_each(|key| *key = next());
+        keys
+    };
+}
+
+pub(super) fn piece_key(square: Square, piece: Piece) -> u64 {
+    let index = piece.color as usize * 6 + piece.kind as usize;
+    KEYS.pieces[index][(square.rank * 8 + square.file) as usize]
+}
+
+pub(super) fn active_key(active: Color) -> u64 {
+    match active {
+        Color::White => 0,
+        Color::Black => KEYS.black_to_move,
+    }
+}
+
+pub(super) fn castling_key(castling: &Castling) -> u64 {
+    let mut key = 0;
+    for &color in &[Color::White, Color::Black] {
+        for &side in &[Side::King, Side::Queen] {
+            if castling[(color, side)] {
+                key ^= KEYS.castling[color as usize][side as usize];
+            }
+        }
+    }
+    key
+}
+
+impl Board {
+    /// the 64 bit Zobrist hash of the position: pieces, side to move, castling rights and en passant file.
+    /// it is updated incrementally by `apply` and `unapply`.
+    pub fn zobrist(&self) -> u64 {
+        self.zobrist
+    }
+
+    /// recompute the hash from scratch. needed after changing the public fields directly.
+    pub fn rehash(&mut self) {
+        self.zobrist = self.compute_zobrist();
+    }
+
+    pub(super) fn compute_zobrist(&self) -> u64 {
+        let mut key = active_key(self.active) ^ castling_key(&self.castling) ^ self.en_passant_key();
+        for square in Board::squares() {
+            if let Some(piece) = self[square] {
+                key ^= piece_key(square, piece);
+            }
+        }
+        key
+    }
+
+    /// the en passant file only counts if a pawn of the side to move can legally take,
+    /// positions are the same under the FIDE rules otherwise
+    pub(super) fn en_passant_key(&self) -> u64 {
+        match self.en_passant {
+            Some(target) => {
+                let fwd = if self.active == Color::White { -1 } else { 1 };
+                let pawn = Some(Piece::new(self.active, Kind::Pawn));
+                let capturable = [-1, 1]
+                    .iter()
+                    .map(|df| Square::new(target.file + df, target.rank - fwd))
+                    .any(|sq| sq.valid() && self[sq] == pawn && self.en_passant_is_legal(sq, target));
+                if capturable {
+                    KEYS.en_passant[target.file as usize]
+                } else {
+                    0
+                }
+            }
+            None => 0,
+        }
+    }
+
+    /// does taking en passant leave the own king safe
+    fn en_passant_is_legal(&self, origin: Square, target: Square) -> bool {
+        self.is_legal(&Move::new_en_passant(Piece::new(self.active, Kind::Pawn), origin, target))
+    }
+}
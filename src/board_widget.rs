@@ -1,10 +1,10 @@
 use druid::piet::d2d::Bitmap;
 use druid::piet::{D2DTextLayout, ImageFormat, InterpolationMode, Text, TextLayoutBuilder};
-use druid::{Env, Rect, RenderContext, Size, Widget};
+use druid::{Data, Env, Rect, RenderContext, Size, Widget};
 use log::{trace, warn};
 
 use crate::grid::Grid;
-use crate::{Board, Color, Kind, Move, MoveKind, Piece, Square};
+use crate::{Color, Game, Kind, Move, MoveKind, Piece, Square};
 
 pub struct BoardWidget {
     selected: Option<(Square, Vec<Move>)>,
@@ -23,25 +23,36 @@ impl BoardWidget {
 
     fn find_selected_move(&self, square: Square) -> Option<&Move> {
         if let Some((_, moves)) = &self.selected {
-            if let Some(mv) = moves.iter().find(|mv| mv.target == square) {
+            if let Some(mv) = moves.iter().find(|mv| Self::move_squares(mv).contains(&square)) {
                 return Some(mv);
             }
         }
         None
     }
 
+    /// the squares to click for a move. castling can also be chosen with the rook,
+    /// as the king may not move at all in Chess960.
+    fn move_squares(mv: &Move) -> Vec<Square> {
+        match mv.kind {
+            MoveKind::Castle(rook, _) if mv.target == mv.origin => vec![rook],
+            MoveKind::Castle(rook, _) => vec![mv.target, rook],
+            _ => vec![mv.target],
+        }
+    }
+
     pub fn move_color<'a,'b>(mv: &Move, env: &Env) -> druid::Color {
         match mv.kind {
             MoveKind::Move() => env.get(theme::MOVE),
             MoveKind::Take(_) => env.get(theme::TAKE),
             MoveKind::EnPassant() => env.get(theme::EN_PASSANT),
             MoveKind::Castle(_, _) => env.get(theme::CASTLE),
+            MoveKind::Promote(_) | MoveKind::PromoteTake(_, _) => env.get(theme::PROMOTE),
         }
     }
 }
 
-impl Widget<Board> for BoardWidget {
-    fn paint(&mut self, ctx: &mut druid::PaintCtx, data: &Board, env: &Env) {
+impl Widget<Game> for BoardWidget {
+    fn paint(&mut self, ctx: &mut druid::PaintCtx, data: &Game, env: &Env) {
         let grid = Grid::new(ctx.size());
 
         for i in 0..8 {
@@ -89,8 +100,10 @@ impl Widget<Board> for BoardWidget {
 
                 for mv in moves {
                     let c = Self::move_color(mv, &env);
-                    let rect = grid.rect(mv.target).inflate(-5.0, -5.0);
-                    ctx.stroke(rect, &c, 5.0);
+                    for square in Self::move_squares(mv) {
+                        let rect = grid.rect(square).inflate(-5.0, -5.0);
+                        ctx.stroke(rect, &c, 5.0);
+                    }
                 }
             }
         }
@@ -101,14 +114,14 @@ impl Widget<Board> for BoardWidget {
             for i in 0..8 {
                 let rect = grid.square(i, j);
 
-                if let Some(piece) = data.piece(j, i) {
+                if let Some(piece) = data.board().piece(j, i) {
                     sprites.draw(piece, rect);
                 }
             }
         }
     }
 
-    fn event(&mut self, ctx: &mut druid::EventCtx, _event: &druid::Event, board: &mut Board, _env: &Env) {
+    fn event(&mut self, ctx: &mut druid::EventCtx, _event: &druid::Event, game: &mut Game, _env: &Env) {
         let grid = Grid::new(ctx.size());
 
         match _event {
@@ -117,12 +130,12 @@ impl Widget<Board> for BoardWidget {
 
                 let next = if let Some(mv) = self.find_selected_move(square) {
                     // click on a square of the move list of the selected piece
-                    board.apply(mv);
+                    game.play(*mv);
                     None
-                } else if let Some(Piece { color, kind: _ }) = board[square] {
+                } else if let Some(Piece { color, kind: _ }) = game.board()[square] {
                     // click on a square with a piece?
-                    if color == board.active {
-                        let moves = board.get_valid_moves(square);
+                    if color == game.board().active && !game.status().is_over() {
+                        let moves = game.board().get_legal_moves(square);
                         Some((square, moves))
                     } else {
                         None
@@ -139,7 +152,7 @@ impl Widget<Board> for BoardWidget {
         }
     }
 
-    fn layout(&mut self, _ctx: &mut druid::LayoutCtx, bc: &druid::BoxConstraints, _data: &Board, _env: &Env) -> druid::Size {
+    fn layout(&mut self, _ctx: &mut druid::LayoutCtx, bc: &druid::BoxConstraints, _data: &Game, _env: &Env) -> druid::Size {
         trace!("{:?}", bc);
         // let size = if bc.is_width_bounded() && bc.is_height_bounded() {
         //     let size = Size::new(1328.0, 1328.0);
@@ -151,10 +164,15 @@ impl Widget<Board> for BoardWidget {
         self.size
     }
 
-    fn lifecycle(&mut self, _ctx: &mut druid::LifeCycleCtx, _event: &druid::LifeCycle, _data: &Board, _env: &Env) {}
+    fn lifecycle(&mut self, _ctx: &mut druid::LifeCycleCtx, _event: &druid::LifeCycle, _data: &Game, _env: &Env) {}
+
+    fn update(&mut self, ctx: &mut druid::UpdateCtx, old_data: &Game, data: &Game, _env: &Env) {
+        // the selected moves belong to the previous position, e.g. after undo or redo
+        if !old_data.board().same(data.board()) {
+            self.selected = None;
+            ctx.request_paint();
+        }
 
-    fn update(&mut self, ctx: &mut druid::UpdateCtx, _old_data: &Board, _data: &Board, _env: &Env) {
-        
         let grid = Grid::new(ctx.size());
         const O: f64 = -2.0;
 
@@ -205,6 +223,7 @@ pub mod theme {
     pub const TAKE: Key<Color> = Key::new("take");
     pub const EN_PASSANT: Key<Color> = Key::new("en_passant");
     pub const CASTLE: Key<Color> = Key::new("castle");
+    pub const PROMOTE: Key<Color> = Key::new("promote");
 }
 
 struct Sprites<'x, 'a, 'b, 'c> {
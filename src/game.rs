@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use druid::{Data, Lens};
+use log::info;
+
+use crate::{Board, Color, DrawReason, GameStatus, Move, SanError, Undo};
+
+/// a game is the starting position and the list of moves played from there.
+/// the current position can be any ply between the start and the last move,
+/// moves after the current ply can be redone until a different move is played.
+#[derive(Clone, Data, Lens)]
+pub struct Game {
+    start: Board,
+    moves: Arc<Vec<Move>>,
+    // the hashes of the positions after 0, 1, .. moves
+    hashes: Arc<Vec<u64>>,
+    ply: usize,
+    // the position after `ply` moves
+    board: Board,
+    // what it takes to go back from `board`, one for each of the `ply` moves
+    undos: Arc<Vec<Undo>>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new(Board::default())
+    }
+}
+
+impl Game {
+    pub fn new(start: Board) -> Self {
+        Game {
+            board: start.clone(),
+            hashes: Arc::new(vec![start.zobrist()]),
+            start,
+            moves: Arc::new(vec![]),
+            ply: 0,
+            undos: Arc::new(vec![]),
+        }
+    }
+
+    /// the position the game started from
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// the current position
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// all moves of the game, including the ones after the current ply
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// all moves of the game in Standard Algebraic Notation
+    pub fn san_moves(&self) -> Vec<String> {
+        let mut board = self.start.clone();
+        let mut result = vec![];
+        for mv in self.moves.iter() {
+            result.push(board.to_san(mv));
+            board.apply(mv);
+        }
+        result
+    }
+
+    /// the number of moves played to reach the current position
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// play a move in the current position, dropping any moves that could have been redone
+    pub fn play(&mut self, mv: Move) {
+        let dots = if self.board.active == Color::White { "." } else { "..." };
+        info!("{}{} {}", self.board.fullmove_number, dots, self.board.to_san(&mv));
+        let moves = Arc::make_mut(&mut self.moves);
+        moves.truncate(self.ply);
+        moves.push(mv);
+        let undo = self.board.apply(&mv);
+        Arc::make_mut(&mut self.undos).push(undo);
+        self.ply += 1;
+        let hashes = Arc::make_mut(&mut self.hashes);
+        hashes.truncate(self.ply);
+        hashes.push(self.board.zobrist());
+    }
+
+    /// play a move given in Standard Algebraic Notation
+    pub fn play_san(&mut self, san: &str) -> Result<(), SanError> {
+        let mv = self.board.parse_san(san)?;
+        self.play(mv);
+        Ok(())
+    }
+
+    /// go to the position after the given number of moves
+    pub fn goto(&mut self, ply: usize) {
+        assert!(ply <= self.moves.len(), "ply {} is beyond the end of the game", ply);
+        while self.ply > ply {
+            self.undo();
+        }
+        while self.ply < ply {
+            self.redo();
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.ply > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.ply < self.moves.len()
+    }
+
+    /// step back one move, returns false if already at the start
+    pub fn undo(&mut self) -> bool {
+        if !self.can_undo() {
+            return false;
+        }
+        let undo = Arc::make_mut(&mut self.undos).pop().expect("one undo for each ply");
+        self.board.unapply(undo);
+        self.ply -= 1;
+        true
+    }
+
+    /// step forward one move, returns false if already at the last move
+    pub fn redo(&mut self) -> bool {
+        if !self.can_redo() {
+            return false;
+        }
+        let mv = self.moves[self.ply];
+        let undo = self.board.apply(&mv);
+        Arc::make_mut(&mut self.undos).push(undo);
+        self.ply += 1;
+        true
+    }
+
+    /// how often the current position occurred so far, including now.
+    /// positions are the same if the same pieces are on the same squares, the same player is to move
+    /// and the castling rights and possible en passant captures are the same.
+    // https://en.wikipedia.org/wiki/Threefold_repetition
+    pub fn repetitions(&self) -> usize {
+        // positions can't repeat across a capture or pawn move
+        let since = self.ply.saturating_sub(self.board.halfmove_clock as usize);
+        let current = self.hashes[self.ply];
+        self.hashes[since..=self.ply].iter().filter(|&&hash| hash == current).count()
+    }
+
+    /// the status of the current position including draws by repetition.
+    /// draws by threefold repetition and the fifty-move rule are reported unless the player to move is in check,
+    /// but the game goes on until the fivefold repetition or the seventy-five-move rule end it.
+    pub fn status(&self) -> GameStatus {
+        let status = self.board.status();
+        match status {
+            GameStatus::Checkmate(_) | GameStatus::Stalemate => status,
+            _ if self.repetitions() >= 5 => GameStatus::Draw(DrawReason::FivefoldRepetition),
+            GameStatus::Draw(reason) if reason.is_automatic() => status,
+            GameStatus::Check => status,
+            _ if self.repetitions() >= 3 => GameStatus::Draw(DrawReason::ThreefoldRepetition),
+            _ => status,
+        }
+    }
+}
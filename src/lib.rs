@@ -11,18 +11,30 @@ mod board;
 mod board_widget;
 mod color;
 mod dir;
+mod game;
 mod grid;
 mod kind;
 mod moves;
+mod perft;
+mod pgn;
 mod piece;
+mod san;
+mod search;
 mod square;
+mod status;
 mod valuation;
 
 pub use crate::board::*;
 pub use crate::board_widget::*;
 pub use crate::color::*;
 pub use crate::dir::*;
+pub use crate::game::*;
 pub use crate::kind::*;
 pub use crate::moves::*;
+pub use crate::pgn::*;
 pub use crate::piece::*;
+pub use crate::san::*;
+pub use crate::search::*;
 pub use crate::square::*;
+pub use crate::status::*;
+pub use crate::valuation::*;
@@ -1,13 +1,14 @@
-use druid::widget::{Button, Flex, Label};
-use druid::{AppLauncher, Color, Env, LocalizedString, Widget, WidgetExt, WindowDesc};
-use schach::{Board, BoardWidget};
+use druid::widget::{Button, Flex, Label, LineBreaking};
+use druid::{commands, AppDelegate, AppLauncher, Color, Command, DelegateCtx, Env, Event, FileDialogOptions, FileSpec, Handled, HotKey, KbKey, LocalizedString, SysMods, Target, Widget, WidgetExt, WindowDesc, WindowId};
+use log::{error, info};
+use schach::{BoardWidget, Game, PgnGame};
 
 
 fn main() {
     stderrlog::new().module(module_path!()).verbosity(4).init().expect("log setup failed");
 
     // describe the main window
-    const WINDOW_TITLE: LocalizedString<Board> = LocalizedString::new("Schach!");
+    const WINDOW_TITLE: LocalizedString<Game> = LocalizedString::new("Schach!");
     let main_window = WindowDesc::new(build_root_widget)
         .title(WINDOW_TITLE)
         .window_size((800.0, 1000.0))
@@ -15,20 +16,21 @@ fn main() {
         .set_window_state(druid::WindowState::RESTORED);
 
     // create the initial app state
-    let initial_state = Board::default();
-    // let initial_state = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq").unwrap();
+    let initial_state = Game::default();
+    // let initial_state = Game::new(schach::Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq").unwrap());
 
     // start the application 
     AppLauncher::with_window(main_window)
         // .use_simple_logger()
         .configure_env(configure_env)
+        .delegate(Delegate)
         .launch(initial_state)
         .expect("Failed to launch application");
 }
 
 
 
-fn configure_env(env: &mut Env, _board: &Board)
+fn configure_env(env: &mut Env, _game: &Game)
 {
     env.set(schach::theme::GRID_LINE, Color::rgb8(0x40, 0x40, 0x40));
     env.set(schach::theme::LIGHT, Color::rgb8(128, 128, 128));
@@ -39,18 +41,46 @@ fn configure_env(env: &mut Env, _board: &Board)
     env.set(schach::theme::TAKE, Color::rgb8(255, 0, 0));
     env.set(schach::theme::EN_PASSANT, Color::rgb8(255, 128, 0));
     env.set(schach::theme::CASTLE, Color::rgb8(255, 192, 0));
+    env.set(schach::theme::PROMOTE, Color::rgb8(0, 192, 255));
 }
 
 const VERTICAL_WIDGET_SPACING: f64 = 20.0;
-
-fn build_root_widget() -> impl Widget<Board> {
-    let label = Label::new(|board: &Board, _env: &druid::Env| format!("{:?}'s move ({})", board.active, board.fullmove_number + 1));
-
-    let castling = Label::new(|board: &Board, _env: &druid::Env| format!("castling: {}", board.castling));
-    let en_passant = Label::new(|board: &Board, _env: &druid::Env| format!("en_passant: {:?}", board.en_passant));
-    let captures = Label::new(|board: &Board, _env: &druid::Env| format!("captures: {}", board.captures));
-
-    let reset = Button::new("reset").on_click(|_ctx, data: &mut Board, _env| *data = Board::default());
+/// plies the computer looks ahead, the search runs in the UI thread
+const SEARCH_DEPTH: u32 = 3;
+
+fn build_root_widget() -> impl Widget<Game> {
+    let label = Label::new(|game: &Game, _env: &druid::Env| match game.status() {
+        status if status.is_over() => format!("{}", status),
+        status => format!("{:?}'s move ({}), {}", game.board().active, game.board().fullmove_number, status),
+    });
+
+    let castling = Label::new(|game: &Game, _env: &druid::Env| format!("castling: {}", game.board().castling));
+    let en_passant = Label::new(|game: &Game, _env: &druid::Env| format!("en_passant: {:?}", game.board().en_passant));
+    let captures = Label::new(|game: &Game, _env: &druid::Env| format!("captures: {}", game.board().captures));
+    let ply = Label::new(|game: &Game, _env: &druid::Env| format!("move {} of {}", game.ply(), game.moves().len()));
+    let moves = Label::new(|game: &Game, _env: &druid::Env| move_list(game)).with_line_break_mode(LineBreaking::WordWrap);
+
+    let undo = Button::new("undo").on_click(|_ctx, game: &mut Game, _env| {
+        game.undo();
+    });
+    let redo = Button::new("redo").on_click(|_ctx, game: &mut Game, _env| {
+        game.redo();
+    });
+    let reset = Button::new("reset").on_click(|_ctx, data: &mut Game, _env| *data = Game::default());
+    let computer = Button::new("computer move").on_click(|_ctx, game: &mut Game, _env| {
+        if game.status().is_over() {
+            return;
+        }
+        let result = game.board().search(SEARCH_DEPTH);
+        info!("search: score {}, {} nodes", result.score, result.nodes);
+        if let Some(mv) = result.best_move {
+            game.play(mv);
+        }
+    });
+    let save = Button::new("save game").on_click(|ctx, _game: &mut Game, _env| {
+        let options = FileDialogOptions::new().allowed_types(vec![PGN]).default_type(PGN);
+        ctx.submit_command(commands::SHOW_SAVE_PANEL.with(options));
+    });
 
     let board = BoardWidget::new();
 
@@ -62,7 +92,17 @@ fn build_root_widget() -> impl Widget<Board> {
         .with_spacer(VERTICAL_WIDGET_SPACING)
         .with_child(captures)
         .with_spacer(VERTICAL_WIDGET_SPACING)
+        .with_child(ply)
+        .with_spacer(VERTICAL_WIDGET_SPACING)
+        .with_child(moves.fix_width(250.0))
+        .with_spacer(VERTICAL_WIDGET_SPACING)
+        .with_child(Flex::row().with_child(undo).with_child(redo))
+        .with_spacer(VERTICAL_WIDGET_SPACING)
         .with_child(reset)
+        .with_spacer(VERTICAL_WIDGET_SPACING)
+        .with_child(computer)
+        .with_spacer(VERTICAL_WIDGET_SPACING)
+        .with_child(save)
         .must_fill_main_axis(true);
 
     let lhs = Flex::column()
@@ -75,3 +115,60 @@ fn build_root_widget() -> impl Widget<Board> {
 
     layout
 }
+
+/// the moves played so far, numbered like "1. e4 e5 2. Nf3"
+fn move_list(game: &Game) -> String {
+    let mut result = String::new();
+    // a game that starts with black to move begins with "1... e5"
+    let offset = if game.start().active == schach::Color::Black { 1 } else { 0 };
+    for (i, san) in game.san_moves().iter().take(game.ply()).enumerate() {
+        let ply = i + offset;
+        let number = game.start().fullmove_number as usize + ply / 2;
+        if ply % 2 == 0 {
+            result.push_str(&format!("{}. ", number));
+        } else if i == 0 {
+            result.push_str(&format!("{}... ", number));
+        }
+        result.push_str(san);
+        result.push(' ');
+    }
+    result
+}
+
+const PGN: FileSpec = FileSpec::new("PGN", &["pgn"]);
+
+/// writes the game as PGN to the file chosen in the save dialog.
+/// also handles the undo and redo keys: key events only reach widgets that have the focus,
+/// but every event passes the delegate first.
+struct Delegate;
+
+impl AppDelegate<Game> for Delegate {
+    fn event(&mut self, _ctx: &mut DelegateCtx, _window_id: WindowId, event: Event, game: &mut Game, _env: &Env) -> Option<Event> {
+        if let Event::KeyDown(key) = &event {
+            // undo with Ctrl+Z or the left arrow, redo with Ctrl+Y, Ctrl+Shift+Z or the right arrow
+            if HotKey::new(SysMods::Cmd, "z").matches(key) || HotKey::new(None, KbKey::ArrowLeft).matches(key) {
+                game.undo();
+                return None;
+            }
+            if HotKey::new(SysMods::Cmd, "y").matches(key) || HotKey::new(SysMods::CmdShift, "Z").matches(key) || HotKey::new(None, KbKey::ArrowRight).matches(key) {
+                game.redo();
+                return None;
+            }
+        }
+        Some(event)
+    }
+
+    fn command(&mut self, _ctx: &mut DelegateCtx, _target: Target, cmd: &Command, game: &mut Game, _env: &Env) -> Handled {
+        match cmd.get(commands::SAVE_FILE_AS) {
+            Some(file_info) => {
+                let pgn = PgnGame::new(game.clone()).to_pgn();
+                match std::fs::write(file_info.path(), pgn) {
+                    Ok(()) => info!("saved game to {}", file_info.path().display()),
+                    Err(e) => error!("saving game to {} failed: {}", file_info.path().display(), e),
+                }
+                Handled::Yes
+            }
+            None => Handled::No,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Kind, Piece, Square};
+use crate::{Board, Kind, Piece, Square};
 
 // https://en.wikipedia.org/wiki/Algebraic_notation_(chess)
 
@@ -16,9 +16,53 @@ pub enum MoveKind {
     Take(Kind),
     EnPassant(),
     Castle(Square, Square),
-    // TODO: Promote(Piece, Square, Square, Kind),
+    /// pawn reaches the last rank and is replaced by the given kind
+    Promote(Kind),
+    /// pawn takes a piece (first kind) on the last rank and is replaced by the second kind
+    PromoteTake(Kind, Kind),
 }
 impl Move {
+    /// the kind of piece the pawn is promoted to
+    pub fn promotion(&self) -> Option<Kind> {
+        match self.kind {
+            MoveKind::Promote(kind) | MoveKind::PromoteTake(_, kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// the kind of piece the move takes, a pawn when taking en passant
+    pub fn captured(&self) -> Option<Kind> {
+        match self.kind {
+            MoveKind::Take(kind) | MoveKind::PromoteTake(kind, _) => Some(kind),
+            MoveKind::EnPassant() => Some(Kind::Pawn),
+            _ => None,
+        }
+    }
+
+    pub fn is_capture(&self) -> bool {
+        self.captured().is_some()
+    }
+
+    /// the move in the long algebraic notation of the Universal Chess Interface, e.g. "e2e4", "e1g1" or "e7e8q".
+    /// castling in Chess960 is written as the king taking its own rook, e.g. "b1a1".
+    // http://wbec-ridderkerk.nl/html/UCIProtocol.html
+    pub fn to_uci(&self) -> String {
+        if let MoveKind::Castle(rook, _) = self.kind {
+            let standard = self.origin.file == 4 && (rook.file == 0 || rook.file == 7);
+            if !standard {
+                return format!("{}{}", self.origin, rook);
+            }
+        }
+        let promotion = match self.promotion() {
+            Some(Kind::Queen) => "q",
+            Some(Kind::Rook) => "r",
+            Some(Kind::Bishop) => "b",
+            Some(Kind::Knight) => "n",
+            _ => "",
+        };
+        format!("{}{}{}", self.origin, self.target, promotion)
+    }
+
     pub(crate) fn new_take(piece: Piece, origin: Square, target: Square, kind: Kind) -> Move {
         Move {
             piece,
@@ -54,4 +98,53 @@ impl Move {
             kind: MoveKind::Castle(rook_origin, rook_target),
         }
     }
+
+    pub(crate) fn new_promote(piece: Piece, origin: Square, target: Square, promotion: Kind) -> Move {
+        Move {
+            piece,
+            origin,
+            target,
+            kind: MoveKind::Promote(promotion),
+        }
+    }
+
+    pub(crate) fn new_promote_take(piece: Piece, origin: Square, target: Square, kind: Kind, promotion: Kind) -> Move {
+        Move {
+            piece,
+            origin,
+            target,
+            kind: MoveKind::PromoteTake(kind, promotion),
+        }
+    }
+}
+
+impl Board {
+    /// find the legal move for a move in UCI notation like "e2e4" or "e7e8q".
+    /// castling can also be given as the king taking its own rook like in Chess960, e.g. "e1h1".
+    pub fn parse_uci_move(&self, uci: &str) -> Result<Move, String> {
+        if !uci.is_ascii() || (uci.len() != 4 && uci.len() != 5) {
+            return Err(format!("'{}' doesn't match /[a-h][1-8][a-h][1-8][qrbn]?/", uci));
+        }
+        let origin = Square::from_an(&uci[0..2])?;
+        let target = Square::from_an(&uci[2..4])?;
+        let promotion = match &uci[4..] {
+            "" => None,
+            "q" => Some(Kind::Queen),
+            "r" => Some(Kind::Rook),
+            "b" => Some(Kind::Bishop),
+            "n" => Some(Kind::Knight),
+            other => return Err(format!("'{}' is not a promotion piece in '{}'", other, uci)),
+        };
+        self.get_legal_moves(origin)
+            .into_iter()
+            .find(|mv| {
+                let target_matches = match mv.kind {
+                    // in Chess960 castling by one square or none is only given as king takes rook
+                    MoveKind::Castle(rook, _) => rook == target || (mv.target == target && (mv.target.file - mv.origin.file).abs() == 2),
+                    _ => mv.target == target,
+                };
+                target_matches && mv.promotion() == promotion
+            })
+            .ok_or_else(|| format!("'{}' is not a legal move", uci))
+    }
 }
@@ -0,0 +1,39 @@
+use crate::{Board, Move};
+
+// https://www.chessprogramming.org/Perft
+
+impl Board {
+    /// count the leaf nodes of the legal move tree of the given depth
+    pub fn perft(&self, depth: u32) -> u64 {
+        self.clone().perft_mut(depth)
+    }
+
+    /// perft of the given depth broken down by the first move
+    pub fn divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        self.legal_moves()
+            .into_iter()
+            .map(|mv| {
+                let mut next = self.clone();
+                next.apply(&mv);
+                (mv, next.perft_mut(depth.saturating_sub(1)))
+            })
+            .collect()
+    }
+
+    fn perft_mut(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+        let moves = self.legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+        let mut nodes = 0;
+        for mv in &moves {
+            let undo = self.apply(mv);
+            nodes += self.perft_mut(depth - 1);
+            self.unapply(undo);
+        }
+        nodes
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Display;
+
+use crate::{Game, Move};
+
+mod reader;
+pub use reader::*;
+mod writer;
+
+// https://en.wikipedia.org/wiki/Portable_Game_Notation
+// http://www.saremba.de/chessgml/standards/pgn/pgn-complete.htm
+
+/// the result of a game as written in PGN
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// the game is still going on, was abandoned or the result is unknown
+    Unknown,
+}
+
+impl GameResult {
+    pub fn from_pgn(token: &str) -> Option<GameResult> {
+        match token {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl Display for GameResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "1-0"),
+            GameResult::BlackWins => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Unknown => write!(f, "*"),
+        }
+    }
+}
+
+/// a game read from or written to PGN
+#[derive(Clone)]
+pub struct PgnGame {
+    /// the tag pairs in the order they appear
+    pub tags: Vec<(String, String)>,
+    pub game: Game,
+    pub result: GameResult,
+    /// comments and the number of moves played before them
+    pub comments: Vec<(usize, String)>,
+    /// numeric annotation glyphs and the number of moves played before them
+    pub nags: Vec<(usize, u32)>,
+    /// alternatives to the moves of the game
+    pub variations: Vec<Variation>,
+}
+
+/// a line of moves played instead of a move of the game or of another variation
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Variation {
+    /// the number of moves of the parent line played before the variation,
+    /// its first move replaces the move with this index
+    pub ply: usize,
+    pub moves: Vec<Move>,
+    /// comments and the number of moves of the variation played before them
+    pub comments: Vec<(usize, String)>,
+    /// numeric annotation glyphs and the number of moves of the variation played before them
+    pub nags: Vec<(usize, u32)>,
+    /// alternatives to the moves of this variation
+    pub variations: Vec<Variation>,
+}
+
+impl PgnGame {
+    /// the value of the first tag with the given name
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(n, _)| n == name).map(|(_, value)| value.as_str())
+    }
+}
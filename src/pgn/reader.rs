@@ -0,0 +1,394 @@
+use std::fmt::Display;
+use std::io::BufRead;
+
+use crate::{Board, Game, Move};
+
+use super::{GameResult, PgnGame, Variation};
+
+/// error while reading PGN, lines and columns start at 1
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// read all games of a PGN text
+pub fn read_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    PgnReader::new(text.as_bytes()).collect()
+}
+
+/// reads the games of a PGN file one after the other, without holding the whole file in memory.
+/// after an error in a game the reader skips to the next game.
+pub struct PgnReader<R> {
+    lexer: Lexer<R>,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        PgnReader { lexer: Lexer::new(reader) }
+    }
+
+    fn read_game(&mut self) -> Result<Option<PgnGame>, PgnError> {
+        let mut tags = vec![];
+        while let Some((pos, Token::Tag(name, value))) = self.lexer.peek()? {
+            let (pos, name, value) = (*pos, name.clone(), value.clone());
+            self.lexer.next()?;
+            if name == "FEN" {
+                Board::from_fen(&value).map_err(|e| pos.error(format!("invalid FEN tag: {}", e)))?;
+            }
+            tags.push((name, value));
+        }
+        if tags.is_empty() && self.lexer.peek()?.is_none() {
+            return Ok(None);
+        }
+
+        let start = match tags.iter().find(|(name, _)| name == "FEN") {
+            Some((_, fen)) => Board::from_fen(fen).expect("checked above"),
+            None => Board::default(),
+        };
+        let mut line = Line::new(start.clone());
+        let result = match self.read_line(&mut line)? {
+            // tags without a result token before them start the next game
+            Stop::End => GameResult::Unknown,
+            Stop::Close(pos) => return Err(pos.error("')' without a variation".to_string())),
+            Stop::Result(_, result) => result,
+        };
+        let mut game = Game::new(start);
+        for mv in line.moves {
+            game.play(mv);
+        }
+        Ok(Some(PgnGame {
+            tags,
+            game,
+            result,
+            comments: line.comments,
+            nags: line.nags,
+            variations: line.variations,
+        }))
+    }
+
+    /// a variation up to its ')', the '(' is already read.
+    /// its moves are replayed from the board before the move they replace, so they have to be legal.
+    fn read_variation(&mut self, open: Position, ply: usize, start: Board) -> Result<Variation, PgnError> {
+        let mut line = Line::new(start);
+        match self.read_line(&mut line)? {
+            Stop::End => Err(open.error("variation is not closed".to_string())),
+            Stop::Close(_) => Ok(Variation {
+                ply,
+                moves: line.moves,
+                comments: line.comments,
+                nags: line.nags,
+                variations: line.variations,
+            }),
+            Stop::Result(pos, _) => Err(pos.error("result inside a variation".to_string())),
+        }
+    }
+
+    /// read moves, annotations and variations into the line until something else ends it
+    fn read_line(&mut self, line: &mut Line) -> Result<Stop, PgnError> {
+        loop {
+            let (pos, token) = match self.lexer.peek()? {
+                None | Some((_, Token::Tag(_, _))) => return Ok(Stop::End),
+                Some(_) => self.lexer.next()?.expect("peeked"),
+            };
+            let played = line.moves.len();
+            match token {
+                Token::Tag(_, _) => unreachable!(),
+                Token::Comment(text) => line.comments.push((played, text)),
+                Token::Nag(nag) => line.nags.push((played, nag)),
+                Token::Period => {}
+                Token::Open => {
+                    let board = line.previous.clone().ok_or_else(|| pos.error("variation before the first move".to_string()))?;
+                    let variation = self.read_variation(pos, played - 1, board)?;
+                    line.variations.push(variation);
+                }
+                Token::Close => return Ok(Stop::Close(pos)),
+                Token::Symbol(symbol) => {
+                    if let Some(result) = GameResult::from_pgn(&symbol) {
+                        return Ok(Stop::Result(pos, result));
+                    }
+                    // move numbers
+                    if symbol.chars().all(|c| c.is_ascii_digit()) {
+                        continue;
+                    }
+                    let mv = line.board.parse_san(&symbol).map_err(|e| pos.error(e.to_string()))?;
+                    line.play(mv);
+                }
+            }
+        }
+    }
+
+    /// skip the rest of a broken game, up to its result or the tags of the next game
+    fn recover(&mut self) {
+        loop {
+            match self.lexer.peek() {
+                Ok(None) | Ok(Some((_, Token::Tag(_, _)))) => return,
+                Ok(Some((_, Token::Symbol(symbol)))) if GameResult::from_pgn(symbol).is_some() => {
+                    let _ = self.lexer.next();
+                    return;
+                }
+                Ok(Some(_)) => {
+                    let _ = self.lexer.next();
+                }
+                // the broken token has been consumed, continue with the next game from there
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_game() {
+            Ok(game) => game.map(Ok),
+            Err(error) => {
+                self.recover();
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn error(self, message: String) -> PgnError {
+        PgnError {
+            line: self.line,
+            column: self.column,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Nag(u32),
+    Open,
+    Close,
+    Period,
+    /// moves, move numbers and results
+    Symbol(String),
+}
+
+/// splits the PGN text into tokens, reading the input line by line
+struct Lexer<R> {
+    reader: R,
+    line: Vec<char>,
+    line_number: usize,
+    column: usize,
+    peeked: Option<(Position, Token)>,
+}
+
+impl<R: BufRead> Lexer<R> {
+    fn new(reader: R) -> Self {
+        Lexer {
+            reader,
+            line: vec![],
+            line_number: 0,
+            column: 0,
+            peeked: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&(Position, Token)>, PgnError> {
+        if self.peeked.is_none() {
+            self.peeked = self.read_token()?;
+        }
+        Ok(self.peeked.as_ref())
+    }
+
+    fn next(&mut self) -> Result<Option<(Position, Token)>, PgnError> {
+        match self.peeked.take() {
+            Some(token) => Ok(Some(token)),
+            None => self.read_token(),
+        }
+    }
+
+    /// the position of the next character
+    fn position(&self) -> Position {
+        Position {
+            line: self.line_number,
+            column: self.column + 1,
+        }
+    }
+
+    fn peek_char(&mut self) -> Result<Option<char>, PgnError> {
+        while self.column >= self.line.len() {
+            let mut buffer = String::new();
+            let read = self.reader.read_line(&mut buffer).map_err(|e| self.position().error(e.to_string()))?;
+            if read == 0 {
+                return Ok(None);
+            }
+            self.line = buffer.chars().collect();
+            self.line_number += 1;
+            self.column = 0;
+            // lines starting with '%' are escaped and ignored
+            if self.line.first() == Some(&'%') {
+                self.column = self.line.len();
+            }
+        }
+        Ok(Some(self.line[self.column]))
+    }
+
+    fn next_char(&mut self) -> Result<Option<char>, PgnError> {
+        let c = self.peek_char()?;
+        self.column += 1;
+        Ok(c)
+    }
+
+    fn read_token(&mut self) -> Result<Option<(Position, Token)>, PgnError> {
+        loop {
+            let c = match self.peek_char()? {
+                Some(c) => c,
+                None => return Ok(None),
+            };
+            let pos = self.position();
+            self.next_char()?;
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '[' => self.read_tag(pos)?,
+                '{' => {
+                    let mut text = String::new();
+                    loop {
+                        match self.next_char()? {
+                            Some('}') => break,
+                            Some(c) => text.push(if c == '\n' || c == '\r' { ' ' } else { c }),
+                            None => return Err(pos.error("comment is not closed".to_string())),
+                        }
+                    }
+                    Token::Comment(text.trim().to_string())
+                }
+                ';' => {
+                    let mut text = String::new();
+                    while let Some(c) = self.peek_char()? {
+                        if c == '\n' || c == '\r' {
+                            break;
+                        }
+                        text.push(c);
+                        self.next_char()?;
+                    }
+                    Token::Comment(text.trim().to_string())
+                }
+                '$' => {
+                    let digits = self.read_while(|c| c.is_ascii_digit())?;
+                    Token::Nag(digits.parse().map_err(|_| pos.error("'$' without a number".to_string()))?)
+                }
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '.' => Token::Period,
+                '*' => Token::Symbol("*".to_string()),
+                c if is_symbol(c) => {
+                    let rest = self.read_while(is_symbol)?;
+                    Token::Symbol(format!("{}{}", c, rest))
+                }
+                c => return Err(pos.error(format!("unexpected character '{}'", c))),
+            };
+            return Ok(Some((pos, token)));
+        }
+    }
+
+    /// a tag pair like [Event "F/S Return Match"], the '[' is already read
+    fn read_tag(&mut self, pos: Position) -> Result<Token, PgnError> {
+        self.read_while(char::is_whitespace)?;
+        let name = self.read_while(|c| c.is_ascii_alphanumeric() || c == '_')?;
+        if name.is_empty() {
+            return Err(self.position().error("expected a tag name".to_string()));
+        }
+        self.read_while(char::is_whitespace)?;
+        let at = self.position();
+        if self.next_char()? != Some('"') {
+            return Err(at.error(format!("expected '\"' before the value of tag {}", name)));
+        }
+        let mut value = String::new();
+        loop {
+            match self.next_char()? {
+                Some('"') => break,
+                Some('\\') => match self.next_char()? {
+                    Some(c @ '"') | Some(c @ '\\') => value.push(c),
+                    _ => return Err(pos.error(format!("invalid escape in the value of tag {}", name))),
+                },
+                Some('\n') | None => return Err(pos.error(format!("value of tag {} is not closed", name))),
+                Some(c) => value.push(c),
+            }
+        }
+        self.read_while(char::is_whitespace)?;
+        let at = self.position();
+        if self.next_char()? != Some(']') {
+            return Err(at.error(format!("expected ']' after tag {}", name)));
+        }
+        Ok(Token::Tag(name, value))
+    }
+
+    fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> Result<String, PgnError> {
+        let mut result = String::new();
+        while let Some(c) = self.peek_char()? {
+            if !predicate(c) {
+                break;
+            }
+            result.push(c);
+            self.next_char()?;
+        }
+        Ok(result)
+    }
+}
+
+/// the moves of the game or of a variation while they are read
+struct Line {
+    board: Board,
+    /// the board before the last move, variations of that move start from it
+    previous: Option<Board>,
+    moves: Vec<Move>,
+    comments: Vec<(usize, String)>,
+    nags: Vec<(usize, u32)>,
+    variations: Vec<Variation>,
+}
+
+impl Line {
+    fn new(start: Board) -> Self {
+        Line {
+            board: start,
+            previous: None,
+            moves: vec![],
+            comments: vec![],
+            nags: vec![],
+            variations: vec![],
+        }
+    }
+
+    fn play(&mut self, mv: Move) {
+        self.previous = Some(self.board.clone());
+        self.board.apply(&mv);
+        self.moves.push(mv);
+    }
+}
+
+/// what ends the moves of a line
+enum Stop {
+    /// the end of the input or the tags of the next game, neither is read
+    End,
+    Close(Position),
+    Result(Position, GameResult),
+}
+
+/// characters of moves, move numbers and results
+fn is_symbol(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '#' | '=' | ':' | '-' | '/' | '!' | '?')
+}
@@ -0,0 +1,168 @@
+use std::io::Write;
+
+use crate::{Board, Color, Game, GameStatus, Move};
+
+use super::{GameResult, PgnGame, Variation};
+
+/// the tags every PGN game has, in the order they are written
+// http://www.saremba.de/chessgml/standards/pgn/pgn-complete.htm#c8.1.1
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+const LINE_WIDTH: usize = 80;
+
+impl PgnGame {
+    /// a game without tags, the result is taken from the final position
+    pub fn new(game: Game) -> Self {
+        let result = match game.status() {
+            GameStatus::Checkmate(Color::White) => GameResult::WhiteWins,
+            GameStatus::Checkmate(Color::Black) => GameResult::BlackWins,
+            GameStatus::Stalemate => GameResult::Draw,
+            // draws that nobody claimed don't decide the game
+            GameStatus::Draw(reason) if reason.is_automatic() => GameResult::Draw,
+            _ => GameResult::Unknown,
+        };
+        PgnGame {
+            tags: vec![],
+            game,
+            result,
+            comments: vec![],
+            nags: vec![],
+            variations: vec![],
+        }
+    }
+
+    /// write the game as PGN: the Seven Tag Roster, the other tags,
+    /// and the movetext in SAN wrapped at 80 columns
+    pub fn write<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        for (name, default) in &SEVEN_TAG_ROSTER {
+            let value = match *name {
+                "Result" => self.result.to_string(),
+                _ => self.tag(name).unwrap_or(default).to_string(),
+            };
+            write_tag(out, name, &value)?;
+        }
+        let start = self.game.start();
+        let standard_start = start.to_fen() == Board::default().to_fen();
+        if !standard_start {
+            write_tag(out, "SetUp", "1")?;
+            write_tag(out, "FEN", &start.to_fen())?;
+        }
+        for (name, value) in &self.tags {
+            let written = SEVEN_TAG_ROSTER.iter().any(|(n, _)| n == name) || name == "SetUp" || name == "FEN";
+            if !written {
+                write_tag(out, name, value)?;
+            }
+        }
+        writeln!(out)?;
+
+        let mut line = String::new();
+        for token in self.movetext() {
+            if !line.is_empty() && line.len() + 1 + token.len() > LINE_WIDTH {
+                writeln!(out, "{}", line)?;
+                line.clear();
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&token);
+        }
+        writeln!(out, "{}", line)?;
+        writeln!(out)
+    }
+
+    /// the game as PGN text
+    pub fn to_pgn(&self) -> String {
+        let mut result = vec![];
+        self.write(&mut result).expect("writing to a Vec can't fail");
+        String::from_utf8(result).expect("PGN is written as UTF-8")
+    }
+
+    /// the tokens of the movetext: move numbers, moves, annotations, comments, variations and the result
+    fn movetext(&self) -> Vec<String> {
+        let mut result = vec![];
+        // moves that were taken back in the game are not written
+        let line = Line {
+            moves: &self.game.moves()[..self.game.ply()],
+            comments: &self.comments,
+            nags: &self.nags,
+            variations: &self.variations,
+        };
+        line.write(&mut result, self.game.start().clone());
+        result.push(self.result.to_string());
+        result
+    }
+}
+
+/// the moves of the game or of a variation, with their annotations
+struct Line<'a> {
+    moves: &'a [Move],
+    comments: &'a [(usize, String)],
+    nags: &'a [(usize, u32)],
+    variations: &'a [Variation],
+}
+
+impl Line<'_> {
+    fn variation(variation: &Variation) -> Line<'_> {
+        Line {
+            moves: &variation.moves,
+            comments: &variation.comments,
+            nags: &variation.nags,
+            variations: &variation.variations,
+        }
+    }
+
+    /// the annotations after the given number of moves, returns if there were any
+    fn annotate(&self, result: &mut Vec<String>, ply: usize) -> bool {
+        let mut annotated = false;
+        for (_, nag) in self.nags.iter().filter(|(at, _)| *at == ply) {
+            result.push(format!("${}", nag));
+            annotated = true;
+        }
+        for (_, comment) in self.comments.iter().filter(|(at, _)| *at == ply) {
+            // comments can't contain '}', and splitting them into words lets them wrap
+            result.extend(format!("{{{}}}", comment.replace('}', ")")).split_whitespace().map(String::from));
+            annotated = true;
+        }
+        annotated
+    }
+
+    fn write(&self, result: &mut Vec<String>, mut board: Board) {
+        // black moves get a number at the start and after annotations or variations
+        let mut numbered = true;
+        self.annotate(result, 0);
+        for (ply, mv) in self.moves.iter().enumerate() {
+            if board.active == Color::White {
+                result.push(format!("{}.", board.fullmove_number));
+            } else if numbered {
+                result.push(format!("{}...", board.fullmove_number));
+            }
+            result.push(board.to_san(mv));
+            let before = board.clone();
+            board.apply(mv);
+            numbered = self.annotate(result, ply + 1);
+            for variation in self.variations.iter().filter(|variation| variation.ply == ply) {
+                // the parentheses stick to the first and last token, the variation still wraps
+                let start = result.len();
+                Line::variation(variation).write(result, before.clone());
+                if result.len() == start {
+                    result.push(String::new());
+                }
+                result[start].insert(0, '(');
+                result.last_mut().expect("pushed above").push(')');
+                numbered = true;
+            }
+        }
+    }
+}
+
+fn write_tag<W: Write>(out: &mut W, name: &str, value: &str) -> std::io::Result<()> {
+    writeln!(out, "[{} \"{}\"]", name, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
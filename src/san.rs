@@ -0,0 +1,191 @@
+use std::fmt::Display;
+
+use crate::{Board, Kind, Move, MoveKind, Square};
+
+// https://en.wikipedia.org/wiki/Algebraic_notation_(chess)
+
+/// the letter of a piece in standard algebraic notation, pawns have none
+fn letter(kind: Kind) -> &'static str {
+    match kind {
+        Kind::King => "K",
+        Kind::Queen => "Q",
+        Kind::Rook => "R",
+        Kind::Bishop => "B",
+        Kind::Knight => "N",
+        Kind::Pawn => "",
+    }
+}
+
+/// the kind of piece for a SAN letter, pawns have none
+fn kind(letter: char) -> Option<Kind> {
+    match letter {
+        'K' => Some(Kind::King),
+        'Q' => Some(Kind::Queen),
+        'R' => Some(Kind::Rook),
+        'B' => Some(Kind::Bishop),
+        'N' => Some(Kind::Knight),
+        _ => None,
+    }
+}
+
+/// error returned by `Board::parse_san`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SanError {
+    /// the text is not a move in algebraic notation
+    Syntax(String),
+    /// no legal move matches the text
+    Illegal(String),
+    /// more than one legal move matches the text, the candidates are given in SAN
+    Ambiguous(String, Vec<String>),
+}
+
+impl Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SanError::Syntax(san) => write!(f, "'{}' is not a move in algebraic notation", san),
+            SanError::Illegal(san) => write!(f, "'{}' is not a legal move", san),
+            SanError::Ambiguous(san, candidates) => write!(f, "'{}' is ambiguous, it could be {}", san, candidates.join(", ")),
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+impl Board {
+    /// the move in Standard Algebraic Notation, e.g. "e4", "Nbd2", "exd6", "O-O-O", "e8=Q+" or "Qh4#".
+    /// the move has to be a legal move in this position.
+    pub fn to_san(&self, mv: &Move) -> String {
+        let mut result = match mv.kind {
+            // in Chess960 the king may not move at all, but the rook tells the side
+            MoveKind::Castle(rook, _) if rook.file > mv.origin.file => "O-O".to_string(),
+            MoveKind::Castle(_, _) => "O-O-O".to_string(),
+            _ => {
+                let capture = mv.is_capture();
+                let mut san = String::new();
+                if mv.piece.kind == Kind::Pawn {
+                    // pawn captures are identified by the file they leave
+                    if capture {
+                        san.push_str(&mv.origin.to_string()[..1]);
+                    }
+                } else {
+                    san.push_str(letter(mv.piece.kind));
+                    san.push_str(&self.disambiguation(mv));
+                }
+                if capture {
+                    san.push('x');
+                }
+                san.push_str(&mv.target.to_string());
+                if let Some(kind) = mv.promotion() {
+                    san.push('=');
+                    san.push_str(letter(kind));
+                }
+                san
+            }
+        };
+
+        let mut next = self.clone();
+        next.apply(mv);
+        if next.in_check() {
+            result.push(if next.legal_moves().is_empty() { '#' } else { '+' });
+        }
+        result
+    }
+
+    /// the origin file, rank or square needed to tell the move apart from
+    /// other legal moves of the same kind of piece to the same target
+    fn disambiguation(&self, mv: &Move) -> String {
+        let others: Vec<_> = self
+            .legal_moves()
+            .into_iter()
+            .filter(|other| other.piece == mv.piece && other.target == mv.target && other.origin != mv.origin)
+            .collect();
+        let origin = mv.origin.to_string();
+        if others.is_empty() {
+            String::new()
+        } else if others.iter().all(|other| other.origin.file != mv.origin.file) {
+            origin[..1].to_string()
+        } else if others.iter().all(|other| other.origin.rank != mv.origin.rank) {
+            origin[1..].to_string()
+        } else {
+            origin
+        }
+    }
+
+    /// find the legal move for a move in Standard Algebraic Notation.
+    /// also accepts a missing 'x', castling with zeros ("0-0"),
+    /// lowercase promotion pieces with or without '=' ("e8q"), and check or annotation suffixes.
+    pub fn parse_san(&self, san: &str) -> Result<Move, SanError> {
+        let syntax = || SanError::Syntax(san.to_string());
+        let text = san.trim().trim_end_matches(['+', '#', '!', '?']);
+
+        let castle = match text {
+            "O-O" | "0-0" | "o-o" => Some(true),
+            "O-O-O" | "0-0-0" | "o-o-o" => Some(false),
+            _ => None,
+        };
+        if let Some(king_side) = castle {
+            return self.select(san, |mv| matches!(mv.kind, MoveKind::Castle(rook, _) if (rook.file > mv.origin.file) == king_side));
+        }
+
+        let mut chars: Vec<char> = text.chars().collect();
+        let piece = match chars.first().copied().and_then(kind) {
+            Some(kind) => {
+                chars.remove(0);
+                kind
+            }
+            None => Kind::Pawn,
+        };
+
+        // promotion at the end: "=Q", "Q" or "q", but not the file "b" of a target square
+        let mut promotion = None;
+        if chars.len() > 2 && !chars[chars.len() - 1].is_ascii_digit() {
+            let letter = chars.pop().map(|c| c.to_ascii_uppercase()).and_then(kind).ok_or_else(syntax)?;
+            if chars.last() == Some(&'=') {
+                chars.pop();
+            }
+            promotion = Some(letter);
+        }
+
+        if chars.len() < 2 {
+            return Err(syntax());
+        }
+        let target: String = chars.split_off(chars.len() - 2).into_iter().collect();
+        let target = Square::from_an(&target).map_err(|_| syntax())?;
+
+        let capture = matches!(chars.last(), Some('x') | Some(':'));
+        if capture {
+            chars.pop();
+        }
+
+        // what is left is the optional origin file and/or rank
+        let (mut file, mut rank) = (None, None);
+        for c in chars {
+            match c {
+                'a'..='h' if file.is_none() && rank.is_none() => file = Some(c as i32 - 'a' as i32),
+                '1'..='8' if rank.is_none() => rank = Some(8 - (c as i32 - '0' as i32)),
+                _ => return Err(syntax()),
+            }
+        }
+
+        self.select(san, |mv| {
+            mv.piece.kind == piece
+                && mv.target == target
+                && file.is_none_or(|file| mv.origin.file == file)
+                && rank.is_none_or(|rank| mv.origin.rank == rank)
+                // a missing 'x' is fine, an 'x' on a move that captures nothing is not
+                && (mv.is_capture() || !capture)
+                && mv.promotion() == promotion
+                && !matches!(mv.kind, MoveKind::Castle(_, _))
+        })
+    }
+
+    /// the single legal move matching the predicate
+    fn select(&self, san: &str, predicate: impl Fn(&Move) -> bool) -> Result<Move, SanError> {
+        let candidates: Vec<Move> = self.legal_moves().into_iter().filter(|mv| predicate(mv)).collect();
+        match candidates.len() {
+            0 => Err(SanError::Illegal(san.to_string())),
+            1 => Ok(candidates[0]),
+            _ => Err(SanError::Ambiguous(san.to_string(), candidates.iter().map(|mv| self.to_san(mv)).collect())),
+        }
+    }
+}
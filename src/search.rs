@@ -0,0 +1,192 @@
+use crate::valuation::{DefaultValuation, Valuation};
+use crate::{Board, Kind, Move};
+
+mod transposition;
+pub use transposition::*;
+
+// https://www.chessprogramming.org/Negamax
+// https://www.chessprogramming.org/Alpha-Beta
+// https://www.chessprogramming.org/Quiescence_Search
+
+/// the score of being checkmated right now, mates further away score a little less
+pub const MATE: f32 = 100_000.0;
+/// scores this close to `MATE` are mates, counted in plies
+const MATE_PLIES: f32 = 1_000.0;
+
+/// the outcome of a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    /// the best move found, `None` if there is no legal move
+    pub best_move: Option<Move>,
+    /// the score of the position for the player to move
+    pub score: f32,
+    /// the principal variation, the moves both players are expected to play starting with the best move
+    pub pv: Vec<Move>,
+    /// the number of positions visited
+    pub nodes: u64,
+}
+
+impl Board {
+    /// search the legal moves to the given depth in plies for the best move of the player to move.
+    /// the search goes at least one ply deep.
+    pub fn search(&self, depth: u32) -> SearchResult {
+        self.search_with(depth, &DefaultValuation())
+    }
+
+    /// search like `search`, scoring the positions with the given valuation
+    pub fn search_with<V: Valuation + ?Sized>(&self, depth: u32, valuation: &V) -> SearchResult {
+        Searcher { valuation, table: None, nodes: 0 }.search(self, depth)
+    }
+
+    /// search like `search_with`, remembering the positions in the table.
+    /// the same table can be used for several searches.
+    pub fn search_with_table<V: Valuation + ?Sized>(&self, depth: u32, valuation: &V, table: &mut TranspositionTable) -> SearchResult {
+        Searcher { valuation, table: Some(table), nodes: 0 }.search(self, depth)
+    }
+}
+
+struct Searcher<'a, V: Valuation + ?Sized> {
+    valuation: &'a V,
+    table: Option<&'a mut TranspositionTable>,
+    nodes: u64,
+}
+
+impl<V: Valuation + ?Sized> Searcher<'_, V> {
+    fn search(mut self, board: &Board, depth: u32) -> SearchResult {
+        let mut pv = vec![];
+        // at least one ply, or there would be no move
+        let depth = depth.max(1);
+        let score = self.negamax(&mut board.clone(), depth, 0, -f32::INFINITY, f32::INFINITY, &mut pv);
+        SearchResult {
+            best_move: pv.first().copied(),
+            score,
+            pv,
+            nodes: self.nodes,
+        }
+    }
+
+    /// the score of the position for the player to move, `pv` is filled with the best line
+    fn negamax(&mut self, board: &mut Board, depth: u32, ply: u32, mut alpha: f32, beta: f32, pv: &mut Vec<Move>) -> f32 {
+        self.nodes += 1;
+        pv.clear();
+        let mut moves = board.legal_moves();
+        if moves.is_empty() {
+            return if board.in_check() { -MATE + ply as f32 } else { 0.0 };
+        }
+        // the root still needs a move, even in a drawn position
+        if ply > 0 && (board.halfmove_clock >= 100 || board.insufficient_material()) {
+            return 0.0;
+        }
+        if depth == 0 {
+            return self.quiescence(board, alpha, beta);
+        }
+
+        let key = board.zobrist();
+        let entry = self.table.as_mut().and_then(|table| table.probe(key));
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth && ply > 0) {
+            let score = from_table(entry.score, ply);
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => score >= beta,
+                Bound::Upper => score <= alpha,
+            };
+            if cutoff {
+                pv.extend(entry.best_move.filter(|_| entry.bound == Bound::Exact));
+                return score;
+            }
+        }
+
+        self.order(board, &mut moves);
+        // the best move of an earlier search first, it is likely to be the best again
+        if let Some(index) = entry.and_then(|entry| moves.iter().position(|&mv| Some(mv) == entry.best_move)) {
+            moves[..=index].rotate_right(1);
+        }
+        let original_alpha = alpha;
+        let mut line = vec![];
+        for mv in moves {
+            let undo = board.apply(&mv);
+            let score = -self.negamax(board, depth - 1, ply + 1, -beta, -alpha, &mut line);
+            board.unapply(undo);
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv);
+                pv.extend_from_slice(&line);
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        if let Some(table) = self.table.as_mut() {
+            let bound = if alpha >= beta {
+                Bound::Lower
+            } else if alpha > original_alpha {
+                Bound::Exact
+            } else {
+                Bound::Upper
+            };
+            let best_move = pv.first().copied();
+            table.store(Entry { key, depth, bound, score: to_table(alpha, ply), best_move });
+        }
+        alpha
+    }
+
+    /// follow the captures until the position is quiet, so that the valuation
+    /// isn't taken in the middle of an exchange
+    fn quiescence(&mut self, board: &mut Board, mut alpha: f32, beta: f32) -> f32 {
+        self.nodes += 1;
+        // the player to move doesn't have to take
+        let stand_pat = self.valuation.evaluate(board);
+        if stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut captures: Vec<Move> = board.legal_moves().into_iter().filter(Move::is_capture).collect();
+        self.order(board, &mut captures);
+        for mv in captures {
+            let undo = board.apply(&mv);
+            let score = -self.quiescence(board, -beta, -alpha);
+            board.unapply(undo);
+            if score > alpha {
+                alpha = score;
+            }
+            if alpha >= beta {
+                break;
+            }
+        }
+        alpha
+    }
+
+    /// captures first, the most valuable victims taken by the least valuable attackers first
+    // https://www.chessprogramming.org/MVV-LVA
+    fn order(&self, board: &Board, moves: &mut [Move]) {
+        let value = |kind: Kind| if kind == Kind::King { 0.0 } else { self.valuation.value(board, kind) };
+        moves.sort_by(|a, b| {
+            let key = |mv: &Move| mv.captured().map_or(0.0, |kind| 10.0 * value(kind) - value(mv.piece.kind));
+            key(b).partial_cmp(&key(a)).expect("piece values are numbers")
+        });
+    }
+}
+
+/// mate scores count the plies from the root, the table counts them from the stored position
+fn to_table(score: f32, ply: u32) -> f32 {
+    if score > MATE - MATE_PLIES {
+        score + ply as f32
+    } else if score < -MATE + MATE_PLIES {
+        score - ply as f32
+    } else {
+        score
+    }
+}
+
+fn from_table(score: f32, ply: u32) -> f32 {
+    if score > MATE - MATE_PLIES {
+        score - ply as f32
+    } else if score < -MATE + MATE_PLIES {
+        score + ply as f32
+    } else {
+        score
+    }
+}
@@ -0,0 +1,135 @@
+use std::mem::size_of;
+
+use crate::Move;
+
+// https://www.chessprogramming.org/Transposition_Table
+
+/// how the stored score relates to the real score of the position
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Bound {
+    /// the score is exact
+    Exact,
+    /// the search failed high, the real score is at least this
+    Lower,
+    /// the search failed low, the real score is at most this
+    Upper,
+}
+
+/// what the search found out about a position
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Entry {
+    /// the Zobrist hash of the position
+    pub key: u64,
+    /// the plies searched below the position
+    pub depth: u32,
+    pub bound: Bound,
+    pub score: f32,
+    /// the best move or the move that caused the cutoff, `None` if every move failed low
+    pub best_move: Option<Move>,
+}
+
+/// how well the table is used
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TranspositionStats {
+    /// the number of lookups
+    pub probes: u64,
+    /// the number of lookups that found the position
+    pub hits: u64,
+    /// the number of slots in use
+    pub filled: usize,
+    /// the number of slots
+    pub capacity: usize,
+}
+
+impl TranspositionStats {
+    /// the share of lookups that found the position, from 0.0 to 1.0
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.probes as f64
+    }
+
+    /// the share of slots in use, from 0.0 to 1.0
+    pub fn fill(&self) -> f64 {
+        self.filled as f64 / self.capacity as f64
+    }
+}
+
+/// a fixed size table of search results, the hash of a position picks its slot.
+/// a new entry replaces the one in its slot unless that one was searched deeper.
+/// the table can be kept from one search to the next, `clear` it for a new game.
+pub struct TranspositionTable {
+    slots: Vec<Option<Entry>>,
+    filled: usize,
+    probes: u64,
+    hits: u64,
+}
+
+impl TranspositionTable {
+    /// the size of a slot in bytes
+    pub const SLOT_SIZE: usize = size_of::<Option<Entry>>();
+
+    /// a table that takes at most the given number of bytes, but has at least one slot
+    pub fn new(bytes: usize) -> Self {
+        let capacity = (bytes / Self::SLOT_SIZE).max(1);
+        TranspositionTable {
+            slots: vec![None; capacity],
+            filled: 0,
+            probes: 0,
+            hits: 0,
+        }
+    }
+
+    /// a table that takes at most the given number of mebibytes
+    pub fn with_megabytes(megabytes: usize) -> Self {
+        TranspositionTable::new(megabytes << 20)
+    }
+
+    /// the number of entries the table can hold
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn index(&self, key: u64) -> usize {
+        (key % self.slots.len() as u64) as usize
+    }
+
+    /// the entry for the position with the given hash
+    pub fn probe(&mut self, key: u64) -> Option<Entry> {
+        self.probes += 1;
+        let entry = self.slots[self.index(key)].filter(|entry| entry.key == key);
+        if entry.is_some() {
+            self.hits += 1;
+        }
+        entry
+    }
+
+    /// store the entry, unless its slot holds an entry that was searched deeper
+    pub fn store(&mut self, entry: Entry) {
+        let index = self.index(entry.key);
+        match &self.slots[index] {
+            Some(old) if old.depth > entry.depth => return,
+            Some(_) => {}
+            None => self.filled += 1,
+        }
+        self.slots[index] = Some(entry);
+    }
+
+    /// remove all entries and reset the statistics
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.filled = 0;
+        self.probes = 0;
+        self.hits = 0;
+    }
+
+    pub fn stats(&self) -> TranspositionStats {
+        TranspositionStats {
+            probes: self.probes,
+            hits: self.hits,
+            filled: self.filled,
+            capacity: self.capacity(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt::Display;
+
+use crate::{Board, Color, Kind};
+
+// https://en.wikipedia.org/wiki/Rules_of_chess#End_of_the_game
+
+/// the state of the game as seen from the player to move
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameStatus {
+    /// the player to move has legal moves and is not in check
+    Ongoing,
+    /// the player to move is in check but has legal moves
+    Check,
+    /// the player to move is in check and has no legal moves. holds the color of the winner.
+    Checkmate(Color),
+    /// the player to move is not in check but has no legal moves
+    Stalemate,
+    /// the game is drawn for the given reason
+    Draw(DrawReason),
+}
+
+/// https://en.wikipedia.org/wiki/Draw_(chess)
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DrawReason {
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    InsufficientMaterial,
+}
+
+impl DrawReason {
+    /// the game ends immediately, without a claim by one of the players
+    pub fn is_automatic(&self) -> bool {
+        matches!(self, DrawReason::SeventyFiveMoveRule | DrawReason::FivefoldRepetition | DrawReason::InsufficientMaterial)
+    }
+}
+
+impl GameStatus {
+    /// no more moves can be made. draws that a player has to claim don't end the game.
+    pub fn is_over(&self) -> bool {
+        match self {
+            GameStatus::Ongoing | GameStatus::Check => false,
+            GameStatus::Draw(reason) => reason.is_automatic(),
+            GameStatus::Checkmate(_) | GameStatus::Stalemate => true,
+        }
+    }
+}
+
+impl Display for GameStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameStatus::Ongoing => write!(f, "ongoing"),
+            GameStatus::Check => write!(f, "check"),
+            GameStatus::Checkmate(winner) => write!(f, "checkmate, {:?} wins", winner),
+            GameStatus::Stalemate => write!(f, "stalemate"),
+            GameStatus::Draw(reason) => write!(f, "draw ({})", reason),
+        }
+    }
+}
+
+impl Display for DrawReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawReason::FiftyMoveRule => write!(f, "fifty-move rule"),
+            DrawReason::SeventyFiveMoveRule => write!(f, "seventy-five-move rule"),
+            DrawReason::ThreefoldRepetition => write!(f, "threefold repetition"),
+            DrawReason::FivefoldRepetition => write!(f, "fivefold repetition"),
+            DrawReason::InsufficientMaterial => write!(f, "insufficient material"),
+        }
+    }
+}
+
+/// https://en.wikipedia.org/wiki/Fifty-move_rule, counted in halfmoves
+const FIFTY_MOVES: u32 = 100;
+const SEVENTY_FIVE_MOVES: u32 = 150;
+
+impl Board {
+    /// is the king of the player to move under attack
+    pub fn in_check(&self) -> bool {
+        match self.king_square(self.active) {
+            Some(king) => self.is_under_attack(king, -self.active),
+            None => false,
+        }
+    }
+
+    /// compute the status of the game from the position alone.
+    /// a draw under the fifty-move rule can only be claimed, so a check is reported before it.
+    pub fn status(&self) -> GameStatus {
+        let can_move = !self.legal_moves().is_empty();
+        match (self.in_check(), can_move) {
+            // checkmate takes precedence over the move rules
+            (true, false) => GameStatus::Checkmate(-self.active),
+            (false, false) => GameStatus::Stalemate,
+            _ if self.insufficient_material() => GameStatus::Draw(DrawReason::InsufficientMaterial),
+            _ if self.halfmove_clock >= SEVENTY_FIVE_MOVES => GameStatus::Draw(DrawReason::SeventyFiveMoveRule),
+            (true, true) => GameStatus::Check,
+            _ if self.halfmove_clock >= FIFTY_MOVES => GameStatus::Draw(DrawReason::FiftyMoveRule),
+            (false, true) => GameStatus::Ongoing,
+        }
+    }
+
+    /// neither player can checkmate with the remaining pieces: king against king, king and a single minor piece
+    /// against king, or only bishops that all stand on squares of the same color
+    // https://en.wikipedia.org/wiki/Draw_(chess)#Draws_in_all_games
+    pub fn insufficient_material(&self) -> bool {
+        let white = self.material(Color::White);
+        let black = self.material(Color::Black);
+        if white[Kind::Pawn] + black[Kind::Pawn] + white.major_pieces() + black.major_pieces() > 0 {
+            return false;
+        }
+        let minors = white.minor_pieces() + black.minor_pieces();
+        if minors <= 1 {
+            return true;
+        }
+        if white[Kind::Knight] + black[Kind::Knight] > 0 {
+            return false;
+        }
+        // only bishops left, they can't mate if they all run on the same color
+        let mut square_colors = Board::squares()
+            .filter(|&sq| matches!(self[sq], Some(piece) if piece.kind == Kind::Bishop))
+            .map(|sq| (sq.file + sq.rank) % 2);
+        let first = square_colors.next();
+        square_colors.all(|color| Some(color) == first)
+    }
+}
@@ -1,32 +1,75 @@
-use crate::{Board, Kind};
+use crate::{Board, Color, Kind};
 
-trait Valuation {
+mod piece_square;
+pub use piece_square::*;
+
+/// scores positions for the search. custom evaluators implement this trait,
+/// the piece values are the building blocks of the default evaluation.
+pub trait Valuation {
+    /// the value of a piece of the given kind in this position
     fn value(&self, board: &Board, kind: Kind) -> f32;
-}
 
-struct DefaultValuation();
+    /// the value of all pieces of the given color, the king isn't counted
+    fn material(&self, board: &Board, color: Color) -> f32 {
+        let counter = board.material(color);
+        [Kind::Queen, Kind::Rook, Kind::Bishop, Kind::Knight, Kind::Pawn]
+            .iter()
+            .map(|&kind| counter[kind] as f32 * self.value(board, kind))
+            .sum()
+    }
 
-impl Valuation for DefaultValuation {
-    fn value(&self, _board: &Board, kind: Kind) -> f32 {
-        match kind {
-            Kind::Pawn => 1.0,
-            Kind::Knight => 3.0,
-            Kind::Bishop => 3.0,
-            Kind::Rook => 5.0,
-            Kind::Queen => 9.0,
-            Kind::King => f32::MAX,
-        }
+    /// the score of the position for the player to move, higher is better.
+    /// by default the own material minus the material of the opponent.
+    fn evaluate(&self, board: &Board) -> f32 {
+        self.material(board, board.active) - self.material(board, -board.active)
+    }
+}
+
+// https://www.chessprogramming.org/Tapered_Eval
+impl Board {
+    /// how far the game is from the endgame, from 1.0 with all pieces on the board down to 0.0
+    /// when only kings and pawns are left. computed from the remaining non-pawn material.
+    pub fn phase(&self) -> f32 {
+        const WEIGHTS: [(Kind, u32); 4] = [(Kind::Knight, 1), (Kind::Bishop, 1), (Kind::Rook, 2), (Kind::Queen, 4)];
+        const START: u32 = 24;
+        let material = |color| {
+            let counter = self.material(color);
+            WEIGHTS.iter().map(|&(kind, weight)| counter[kind] * weight).sum::<u32>()
+        };
+        // promotions can add more material than there is at the start
+        (material(Color::White) + material(Color::Black)).min(START) as f32 / START as f32
     }
 }
 
-struct SarrattValuation();
+/// interpolate between the middlegame and the endgame value by the phase of the game
+pub fn taper(phase: f32, middlegame: f32, endgame: f32) -> f32 {
+    phase * middlegame + (1.0 - phase) * endgame
+}
+
+/// the usual piece values: pawn 1, knight and bishop 3, rook 5 and queen 9.
+/// in the endgame the king becomes a fighting piece worth about 4.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultValuation();
 
-impl SarrattValuation {
-    fn is_endgame(_board: &Board) -> bool {
-        false
+impl Valuation for DefaultValuation {
+    // https://en.wikipedia.org/wiki/Chess_piece_relative_value#Standard_valuations
+    fn value(&self, board: &Board, kind: Kind) -> f32 {
+        let (middlegame, endgame) = match kind {
+            Kind::Pawn => (1.0, 1.0),
+            Kind::Knight => (3.0, 3.0),
+            Kind::Bishop => (3.0, 3.0),
+            Kind::Rook => (5.0, 5.0),
+            Kind::Queen => (9.0, 9.0),
+            Kind::King => (f32::MAX, 4.0),
+        };
+        taper(board.phase(), middlegame, endgame)
     }
 }
 
+/// the piece values of William Sarratt
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SarrattValuation();
+
 impl Valuation for SarrattValuation {
     // https://en.wikipedia.org/wiki/Chess_piece_relative_value#cite_note-1
     // pawn 2 at the start, 3+3⁄4 in the endgame;
@@ -35,22 +78,16 @@ impl Valuation for SarrattValuation {
     // rook 15;
     // queen 23+3⁄4;
     // king as attacking piece (in the endgame) 6+1⁄2;
-    // these values are divided by 3 and rounded
+    // these values are divided by 3, so that the pieces are worth about as much as in `DefaultValuation`
     fn value(&self, board: &Board, kind: Kind) -> f32 {
-        let base = match kind {
-            Kind::Pawn => match Self::is_endgame(board) {
-                true => 2.0,
-                false => 3.75,
-            },
-            Kind::Knight => 9.25,
-            Kind::Bishop => 9.75,
-            Kind::Rook => 15.0,
-            Kind::Queen => 23.75,
-            Kind::King => match Self::is_endgame(board) {
-                true => 6.5,
-                false => f32::MAX,
-            },
+        let (middlegame, endgame) = match kind {
+            Kind::Pawn => (2.0, 3.75),
+            Kind::Knight => (9.25, 9.25),
+            Kind::Bishop => (9.75, 9.75),
+            Kind::Rook => (15.0, 15.0),
+            Kind::Queen => (23.75, 23.75),
+            Kind::King => (f32::MAX, 6.5),
         };
-        (base / 3.0f32).round()
+        taper(board.phase(), middlegame, endgame) / 3.0
     }
 }
@@ -0,0 +1,161 @@
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::{Board, Color, Kind, Piece, Square};
+
+use super::{taper, Valuation};
+
+// https://www.chessprogramming.org/Piece-Square_Tables
+
+const KINDS: [Kind; 6] = [Kind::King, Kind::Queen, Kind::Rook, Kind::Knight, Kind::Bishop, Kind::Pawn];
+const MIDDLEGAME: usize = 0;
+const ENDGAME: usize = 1;
+
+/// error while reading piece-square tables, lines start at 1
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TableError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for TableError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// piece values plus a bonus for the square each piece stands on, with separate tables
+/// for the middlegame and the endgame that are tapered by the phase of the game.
+/// the tables are seen from white's side, black uses them mirrored.
+///
+/// the text format has a table for each kind of piece and phase: a line with the kind, the phase
+/// and the value of the piece, e.g. `knight middlegame 320`, followed by eight lines of eight bonuses,
+/// starting with the 8th rank. all numbers are in centipawns, lines starting with `#` are comments.
+/// the default tables in `simplified.txt` show the format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieceSquareValuation {
+    /// by phase and kind, in centipawns
+    values: [[f32; 6]; 2],
+    /// by phase and kind, indexed like the bitboards, in centipawns.
+    /// whole centipawns add up without rounding errors, so equal positions score exactly the same.
+    tables: [[[f32; 64]; 6]; 2],
+}
+
+impl Default for PieceSquareValuation {
+    /// the tables of the Simplified Evaluation Function
+    fn default() -> Self {
+        PieceSquareValuation::from_text(include_str!("simplified.txt")).expect("valid default tables")
+    }
+}
+
+impl PieceSquareValuation {
+    /// read the tables from text, see the type for the format
+    pub fn from_text(text: &str) -> Result<Self, TableError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(n, line)| (n + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        let mut found = [[false; 6]; 2];
+        let mut result = PieceSquareValuation { values: [[0.0; 6]; 2], tables: [[[0.0; 64]; 6]; 2] };
+
+        while let Some((line, header)) = lines.next() {
+            let error = |message: String| TableError { line, message };
+            let fields: Vec<&str> = header.split_whitespace().collect();
+            let (kind, phase, value) = match fields[..] {
+                [kind, phase, value] => (kind, phase, value),
+                _ => return Err(error(format!("expected kind, phase and value instead of '{}'", header))),
+            };
+            let kind = match kind.to_ascii_lowercase().as_str() {
+                "king" => Kind::King,
+                "queen" => Kind::Queen,
+                "rook" => Kind::Rook,
+                "bishop" => Kind::Bishop,
+                "knight" => Kind::Knight,
+                "pawn" => Kind::Pawn,
+                _ => return Err(error(format!("'{}' is not a kind of piece", kind))),
+            } as usize;
+            let phase = match phase.to_ascii_lowercase().as_str() {
+                "middlegame" => MIDDLEGAME,
+                "endgame" => ENDGAME,
+                _ => return Err(error(format!("'{}' is neither middlegame nor endgame", phase))),
+            };
+            if found[phase][kind] {
+                return Err(error(format!("second table for {}", header)));
+            }
+            found[phase][kind] = true;
+            result.values[phase][kind] = number(value).ok_or_else(|| error(format!("'{}' is not a number", value)))?;
+
+            for rank in 0..8 {
+                let (line, row) = lines.next().ok_or_else(|| error(format!("the table ends after {} ranks", rank)))?;
+                let bonuses: Vec<f32> = row
+                    .split_whitespace()
+                    .map(|bonus| number(bonus).ok_or_else(|| TableError { line, message: format!("'{}' is not a number", bonus) }))
+                    .collect::<Result<_, _>>()?;
+                if bonuses.len() != 8 {
+                    return Err(TableError { line, message: format!("{} squares instead of 8", bonuses.len()) });
+                }
+                result.tables[phase][kind][rank * 8..rank * 8 + 8].copy_from_slice(&bonuses);
+            }
+        }
+
+        for (phase, name) in [(MIDDLEGAME, "middlegame"), (ENDGAME, "endgame")] {
+            if let Some(kind) = KINDS.iter().find(|&&kind| !found[phase][kind as usize]) {
+                let line = text.lines().count();
+                return Err(TableError { line, message: format!("no {} table for {:?}", name, kind) });
+            }
+        }
+        Ok(result)
+    }
+
+    /// read the tables from a text file, see the type for the format
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        PieceSquareValuation::from_text(&text).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// the bonus for the piece on the square, tapered by the phase of the game
+    pub fn bonus(&self, board: &Board, piece: Piece, square: Square) -> f32 {
+        let (middlegame, endgame) = self.bonuses(piece, square);
+        taper(board.phase(), middlegame, endgame) / 100.0
+    }
+
+    /// the middlegame and the endgame bonus in centipawns
+    fn bonuses(&self, piece: Piece, square: Square) -> (f32, f32) {
+        // rank 0 is the 8th rank, so white reads the tables as they are written
+        let rank = match piece.color {
+            Color::White => square.rank,
+            Color::Black => 7 - square.rank,
+        };
+        let index = (rank * 8 + square.file) as usize;
+        let kind = piece.kind as usize;
+        (self.tables[MIDDLEGAME][kind][index], self.tables[ENDGAME][kind][index])
+    }
+}
+
+fn number(text: &str) -> Option<f32> {
+    text.parse::<f32>().ok().filter(|value| value.is_finite())
+}
+
+impl Valuation for PieceSquareValuation {
+    fn value(&self, board: &Board, kind: Kind) -> f32 {
+        let kind = kind as usize;
+        taper(board.phase(), self.values[MIDDLEGAME][kind], self.values[ENDGAME][kind]) / 100.0
+    }
+
+    /// the material plus the bonuses of all pieces, the own minus the opponent's
+    fn evaluate(&self, board: &Board) -> f32 {
+        let (mut middlegame, mut endgame) = (0.0, 0.0);
+        for square in Board::squares() {
+            if let Some(piece) = board[square] {
+                let (mg, eg) = self.bonuses(piece, square);
+                let sign = if piece.color == board.active { 1.0 } else { -1.0 };
+                middlegame += sign * mg;
+                endgame += sign * eg;
+            }
+        }
+        self.material(board, board.active) - self.material(board, -board.active) + taper(board.phase(), middlegame, endgame) / 100.0
+    }
+}
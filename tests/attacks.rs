@@ -0,0 +1,66 @@
+use schach::{Board, Color, Kind};
+
+mod common;
+
+use common::square;
+
+fn attackers(board: &Board, an: &str, by: Color) -> Vec<String> {
+    let mut result: Vec<String> = board.attackers_of(square(an), by).iter().map(|sq| sq.to_string()).collect();
+    result.sort();
+    result
+}
+
+#[test]
+fn pawns_and_knights() {
+    let board = Board::from_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").unwrap();
+    // black pawns attack downwards, white ones upwards
+    assert_eq!(attackers(&board, "e4", Color::Black), vec!["d5"]);
+    assert_eq!(attackers(&board, "c4", Color::Black), vec!["d5"]);
+    assert!(!board.is_under_attack(square("d4"), Color::Black));
+    assert!(!board.is_under_attack(square("e6"), Color::Black));
+    assert_eq!(attackers(&board, "d5", Color::White), vec!["c3"]);
+    assert_eq!(attackers(&board, "e2", Color::White), vec!["c3", "e1"]);
+    assert!(!board.is_under_attack(square("c4"), Color::White));
+}
+
+#[test]
+fn sliders_and_blocked_rays() {
+    let board = Board::from_fen("4k3/8/8/8/1b6/8/3P4/R3K2Q w - - 0 1").unwrap();
+    // along the file and the long diagonal
+    assert_eq!(attackers(&board, "a8", Color::White), vec!["a1", "h1"]);
+    assert_eq!(attackers(&board, "h8", Color::White), vec!["h1"]);
+    assert_eq!(attackers(&board, "c1", Color::White), vec!["a1"]);
+    // the queen on h1 sees along the first rank up to the king
+    assert_eq!(attackers(&board, "f1", Color::White), vec!["e1", "h1"]);
+    // but not past it
+    assert_eq!(attackers(&board, "d1", Color::White), vec!["a1", "e1"]);
+    // the pawn on d2 blocks the bishop's ray to e1
+    assert_eq!(attackers(&board, "d2", Color::Black), vec!["b4"]);
+    assert!(!board.is_under_attack(square("e1"), Color::Black));
+    let open = Board::from_fen("4k3/8/8/8/1b6/8/8/R3K2Q w - - 0 1").unwrap();
+    assert_eq!(attackers(&open, "e1", Color::Black), vec!["b4"]);
+}
+
+#[test]
+fn no_castling_out_of_or_through_check() {
+    let castles = |fen: &str| {
+        let board = Board::from_fen(fen).unwrap();
+        let mut result: Vec<String> = board
+            .legal_moves()
+            .iter()
+            .filter(|mv| mv.piece.kind == Kind::King && (mv.origin.file - mv.target.file).abs() == 2)
+            .map(|mv| mv.to_uci())
+            .collect();
+        result.sort();
+        result
+    };
+    assert_eq!(castles("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"), vec!["e1c1", "e1g1"]);
+    // the rook on f8 attacks f1, which the king has to pass
+    assert_eq!(castles("r3kr2/8/8/8/8/8/8/R3K2R w KQq - 0 1"), vec!["e1c1"]);
+    // the rook on c8 attacks c1, where the king would land
+    assert_eq!(castles("2r1k2r/8/8/8/8/8/8/R3K2R w KQk - 0 1"), vec!["e1g1"]);
+    // the king is in check
+    assert_eq!(castles("r3k2r/4r3/8/8/8/8/8/R3K2R w KQkq - 0 1"), Vec::<String>::new());
+    // only the rook passes b1
+    assert_eq!(castles("1r2k3/8/8/8/8/8/8/R3K2R w KQ - 0 1"), vec!["e1c1", "e1g1"]);
+}
@@ -0,0 +1,98 @@
+use std::collections::HashSet;
+
+use schach::{Board, Kind, CHESS960_POSITIONS};
+
+mod common;
+
+use common::check;
+
+// https://www.chessprogramming.org/Chess960_Perft_Results
+
+#[test]
+fn perft() {
+    check("bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9", &[21, 528, 12189]);
+    check("2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9", &[21, 807, 18002]);
+    check("b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9", &[20, 479, 10471]);
+}
+
+#[test]
+fn start_positions() {
+    assert_eq!(Board::chess960(518).to_fen(), Board::default().to_fen());
+    assert_eq!(Board::chess960(0).to_fen(), "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KFkf - 0 1");
+    assert_eq!(Board::chess960(959).to_fen(), "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w CQcq - 0 1");
+
+    let mut positions = HashSet::new();
+    for index in 0..CHESS960_POSITIONS {
+        let board = Board::chess960(index);
+        let rank: Vec<Kind> = (0..8).map(|file| board.piece(7, file).unwrap().kind).collect();
+        let bishops: Vec<usize> = (0..8).filter(|&file| rank[file] == Kind::Bishop).collect();
+        assert_ne!(bishops[0] % 2, bishops[1] % 2, "bishops on the same color in {}", index);
+        let king = rank.iter().position(|&kind| kind == Kind::King).unwrap();
+        assert!(rank[..king].contains(&Kind::Rook) && rank[king..].contains(&Kind::Rook), "king not between the rooks in {}", index);
+        positions.insert(board.to_fen());
+    }
+    assert_eq!(positions.len(), 960);
+}
+
+#[test]
+fn castling_fields() {
+    // Shredder-FEN and X-FEN name the same rooks
+    let shredder = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w HBhb - 0 1").unwrap();
+    let x_fen = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w KQkq - 0 1").unwrap();
+    assert_eq!(shredder.to_fen(), x_fen.to_fen());
+    assert_eq!(shredder.to_fen(), "1r4kr/8/8/8/8/8/8/1R4KR w KBkb - 0 1");
+    assert_eq!(Board::from_fen(&shredder.to_fen()).unwrap().to_fen(), shredder.to_fen());
+
+    assert!(Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w G - 0 1").is_err());
+    assert!(Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w X - 0 1").is_err());
+}
+
+#[test]
+fn castling_with_king_and_rook_anywhere() {
+    let board = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w KBkb - 0 1").unwrap();
+
+    // the king stays on g1, only the rook moves
+    let short = board.parse_san("O-O").unwrap();
+    assert_eq!(short.to_uci(), "g1h1");
+    assert_eq!(board.parse_uci_move("g1h1"), Ok(short));
+    let mut next = board.clone();
+    next.apply(&short);
+    assert_eq!(next.to_fen(), "1r4kr/8/8/8/8/8/8/1R3RK1 b kb - 1 1");
+
+    // the king moves right to left over four squares
+    let long = board.parse_san("O-O-O").unwrap();
+    assert_eq!(long.to_uci(), "g1b1");
+    let mut next = board.clone();
+    let undo = next.apply(&long);
+    assert_eq!(next.to_fen(), "1r4kr/8/8/8/8/8/8/2KR3R b kb - 1 1");
+    next.unapply(undo);
+    assert_eq!(next.to_fen(), board.to_fen());
+    assert_eq!(next.zobrist(), board.zobrist());
+
+    // taking the rook on its start square ends castling on that side
+    let board = Board::from_fen("1r4kr/8/8/8/8/8/8/1R4KR w KBkb - 0 1").unwrap();
+    let mut next = board.clone();
+    next.apply(&board.parse_san("Rxb8+").unwrap());
+    assert_eq!(next.castling.to_string(), "Kk");
+}
+
+#[test]
+fn castling_needs_free_squares_and_a_safe_king() {
+    // the knight on d1 is in the way of the rook
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/RK1N4 w Q - 0 1").unwrap();
+    assert!(board.parse_san("O-O-O").is_err());
+    // the king would land on c1, which the bishop attacks
+    let board = Board::from_fen("4k3/8/8/8/8/4b3/8/RK6 w Q - 0 1").unwrap();
+    assert!(board.parse_san("O-O-O").is_err());
+    // the rook leaves b1 and opens the rank for the queen
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/qR1K4 w Q - 0 1").unwrap();
+    assert!(board.parse_san("O-O-O").is_err());
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/RK6 w Q - 0 1").unwrap();
+    assert_eq!(board.parse_san("O-O-O").map(|mv| mv.to_uci()), Ok("b1a1".to_string()));
+    // the rights are useless for a king and rook away from the home rank
+    let board = Board::from_fen("4k3/8/8/8/4K2R/8/8/8 w K - 0 1").unwrap();
+    assert!(board.legal_moves().iter().all(|mv| mv.to_uci() != "e4g4"));
+    assert!(board.parse_san("O-O").is_err());
+    let board = Board::from_fen("8/8/8/r3k3/8/8/8/4K3 b q - 0 1").unwrap();
+    assert!(board.parse_san("O-O-O").is_err());
+}
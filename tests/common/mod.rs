@@ -0,0 +1,32 @@
+// helpers shared by the integration tests, not every test uses all of them
+#![allow(dead_code)]
+
+use schach::{Board, Square};
+
+/// the square in algebraic notation, e.g. "e4"
+pub fn square(an: &str) -> Square {
+    Square::from_an(an).unwrap()
+}
+
+/// compare the perft results of the position, starting at depth 1
+pub fn check(fen: &str, expected: &[u64]) {
+    let board = Board::from_fen(fen).expect("invalid test position");
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(board.perft(depth as u32 + 1), nodes, "perft({}) of {}", depth + 1, fen);
+    }
+}
+
+/// visit every position up to the given depth, checking that unapply restores the position after each move
+pub fn walk(board: &mut Board, depth: u32, visit: &mut dyn FnMut(&Board)) {
+    visit(board);
+    if depth == 0 {
+        return;
+    }
+    for mv in board.legal_moves() {
+        let before = board.clone();
+        let undo = board.apply(&mv);
+        walk(board, depth - 1, visit);
+        board.unapply(undo);
+        assert!(*board == before, "unapply of {:?} in {} gave {}", mv, before, board);
+    }
+}
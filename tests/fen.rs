@@ -0,0 +1,82 @@
+use schach::{Board, FenError, FenField};
+
+fn error(fen: &str) -> FenError {
+    Board::from_fen(fen).err().expect("invalid FEN accepted")
+}
+
+fn invalid(field: FenField, position: usize, text: &str) -> FenError {
+    FenError::InvalidField { field, position, text: text.to_string() }
+}
+
+#[test]
+fn missing_fields() {
+    assert_eq!(error(""), FenError::MissingField(FenField::Placement));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3"), FenError::MissingField(FenField::ActiveColor));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w"), FenError::MissingField(FenField::Castling));
+    // the last three fields are optional
+    assert!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 w -").is_ok());
+}
+
+#[test]
+fn placement_errors() {
+    assert_eq!(error("9/8/8/8/8/8/8/8 w - - 0 1"), FenError::InvalidRankLength { rank: 8, position: 0, squares: 9 });
+    assert_eq!(error("7/8/8/8/8/8/8/8 w - - 0 1"), FenError::InvalidRankLength { rank: 8, position: 1, squares: 7 });
+    assert_eq!(
+        error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNRR w KQkq - 0 1"),
+        FenError::InvalidRankLength { rank: 1, position: 43, squares: 9 }
+    );
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K2 w - - 0 1"), FenError::InvalidRankLength { rank: 1, position: 19, squares: 7 });
+    assert_eq!(error("4k3/8/8 w - - 0 1"), FenError::InvalidRankCount { position: 7, ranks: 3 });
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3/8 w - - 0 1"), FenError::InvalidRankCount { position: 21, ranks: 9 });
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3/p w - - 0 1"), FenError::InvalidRankCount { position: 21, ranks: 9 });
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3/9 w - - 0 1"), FenError::InvalidRankCount { position: 21, ranks: 9 });
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3/p/8 w - - 0 1"), FenError::InvalidRankCount { position: 23, ranks: 10 });
+    assert_eq!(error("4k3/8/8/8/8/8/8/4X3 w - - 0 1"), FenError::InvalidPiece { position: 17, found: 'X' });
+    assert_eq!(error("4k3/8/8/8/0/8/8/4K3 w - - 0 1"), FenError::InvalidPiece { position: 10, found: '0' });
+}
+
+#[test]
+fn field_errors() {
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"), invalid(FenField::ActiveColor, 20, "x"));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w X - 0 1"), invalid(FenField::Castling, 22, "X"));
+    // white to move, so the pawn that just moved is black's and passed the 6th rank
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - e3 0 1"), invalid(FenField::EnPassant, 24, "e3"));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - - x 1"), invalid(FenField::HalfmoveClock, 26, "x"));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - - 0 0"), invalid(FenField::FullmoveNumber, 28, "0"));
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 w - - 0 1 extra"), FenError::TrailingInput { position: 30 });
+}
+
+#[test]
+fn error_messages() {
+    assert_eq!(error("9/8/8/8/8/8/8/8 w - - 0 1").to_string(), "rank 8 ending at 0 has 9 squares instead of 8");
+    assert_eq!(error("4k3/8/8/8/8/8/8/4K3 x - - 0 1").to_string(), "invalid ActiveColor field 'x' at 20");
+}
+
+#[test]
+fn to_fen_round_trips() {
+    for fen in [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 12 47",
+        "4k3/8/8/8/8/8/8/4K2R b K - 99 80",
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.to_fen(), fen);
+        assert_eq!(board.to_string(), fen);
+        assert!(Board::from_fen(&board.to_fen()).unwrap() == board);
+    }
+    assert_eq!(Board::default().to_fen(), "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+    // the omitted fields are written with their defaults
+    assert_eq!(Board::from_fen("4k3/8/8/8/8/8/8/4K3 b -").unwrap().to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+}
+
+#[test]
+fn to_fen_after_moves() {
+    let mut board = Board::default();
+    for san in ["e4", "c5", "Nf3"] {
+        let mv = board.parse_san(san).unwrap();
+        board.apply(&mv);
+    }
+    assert_eq!(board.to_fen(), "rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+}
@@ -0,0 +1,105 @@
+use schach::{Board, DrawReason, Game, GameResult, GameStatus, PgnGame, Square};
+
+fn play(game: &mut Game, origin: &str, target: &str) {
+    let origin = Square::from_an(origin).unwrap();
+    let target = Square::from_an(target).unwrap();
+    let mv = game.board().get_legal_moves(origin).into_iter().find(|mv| mv.target == target).expect("illegal move");
+    game.play(mv);
+}
+
+#[test]
+fn undo_redo_and_goto() {
+    let mut game = Game::default();
+    play(&mut game, "e2", "e4");
+    play(&mut game, "e7", "e5");
+    play(&mut game, "g1", "f3");
+    let after_three = game.board().to_fen();
+
+    assert!(game.undo());
+    assert!(game.undo());
+    assert_eq!(game.ply(), 1);
+    assert!(game.redo());
+    assert!(game.redo());
+    assert!(!game.redo());
+    assert_eq!(game.board().to_fen(), after_three);
+
+    game.goto(0);
+    assert!(!game.undo());
+    assert_eq!(game.board().to_fen(), Board::default().to_fen());
+}
+
+#[test]
+fn goto_restores_captures() {
+    let mut game = Game::default();
+    play(&mut game, "e2", "e4");
+    play(&mut game, "d7", "d5");
+    play(&mut game, "e4", "d5");
+    let after_capture = game.board().clone();
+
+    game.goto(0);
+    assert!(*game.board() == Board::default());
+    game.goto(3);
+    assert!(*game.board() == after_capture);
+    game.goto(2);
+    assert_eq!(game.board().captures.to_string(), Board::default().captures.to_string());
+}
+
+#[test]
+fn playing_after_undo_drops_the_redo_moves() {
+    let mut game = Game::default();
+    play(&mut game, "e2", "e4");
+    play(&mut game, "e7", "e5");
+    game.undo();
+    play(&mut game, "c7", "c5");
+    assert_eq!(game.moves().len(), 2);
+    assert!(!game.can_redo());
+}
+
+#[test]
+fn repetitions() {
+    let mut game = Game::default();
+    for _ in 0..2 {
+        for &(origin, target) in &[("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")] {
+            play(&mut game, origin, target);
+        }
+    }
+    assert_eq!(game.repetitions(), 3);
+    assert_eq!(game.status(), GameStatus::Draw(DrawReason::ThreefoldRepetition));
+    assert!(!DrawReason::ThreefoldRepetition.is_automatic());
+    // the draw can be claimed, but the game isn't over
+    assert!(!game.status().is_over());
+    assert_eq!(PgnGame::new(game.clone()).result, GameResult::Unknown);
+
+    for _ in 0..2 {
+        for &(origin, target) in &[("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")] {
+            play(&mut game, origin, target);
+        }
+    }
+    assert_eq!(game.repetitions(), 5);
+    assert_eq!(game.status(), GameStatus::Draw(DrawReason::FivefoldRepetition));
+    assert!(game.status().is_over());
+    assert_eq!(PgnGame::new(game.clone()).result, GameResult::Draw);
+}
+
+#[test]
+fn perpetual_check_shows_the_check() {
+    let mut game = Game::new(Board::from_fen("7k/6p1/7p/8/8/8/8/4Q1K1 w - - 0 1").unwrap());
+    for san in &["Qe8+", "Kh7", "Qe4+", "Kh8", "Qe8+", "Kh7", "Qe4+", "Kh8", "Qe8+"] {
+        game.play_san(san).unwrap();
+    }
+    assert_eq!(game.repetitions(), 3);
+    // the repetition can be claimed, but the player to move has to see the check first
+    assert_eq!(game.status(), GameStatus::Check);
+}
+
+#[test]
+fn en_passant_rights_only_count_if_the_capture_is_legal() {
+    // the black pawn on d4 can't take on e3 because it's pinned against its king
+    let pinned = Board::from_fen("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1").unwrap();
+    let without = Board::from_fen("8/8/8/8/k2pP2R/8/8/4K3 b - - 0 1").unwrap();
+    assert_eq!(pinned.zobrist(), without.zobrist());
+
+    let legal = Board::from_fen("8/8/8/8/3pP3/8/k7/4K3 b - e3 0 1").unwrap();
+    let without = Board::from_fen("8/8/8/8/3pP3/8/k7/4K3 b - - 0 1").unwrap();
+    assert_ne!(legal.zobrist(), without.zobrist());
+}
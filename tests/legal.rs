@@ -0,0 +1,36 @@
+use schach::{Board, Square};
+
+fn targets(board: &Board, an: &str) -> Vec<String> {
+    let mut result: Vec<String> = board.get_legal_moves(Square::from_an(an).unwrap()).iter().map(|mv| mv.target.to_string()).collect();
+    result.sort();
+    result
+}
+
+#[test]
+fn pinned_pieces_stay_on_the_line() {
+    // the knight on e2 is pinned by the rook on e8
+    let board = Board::from_fen("4r1k1/8/8/8/8/8/4N3/4K3 w - - 0 1").unwrap();
+    assert_eq!(targets(&board, "e2"), Vec::<String>::new());
+    assert_eq!(board.get_valid_moves(Square::from_an("e2").unwrap()).len(), 6);
+    // the rook on e2 can move along the pin and take the pinning rook
+    let board = Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap();
+    assert_eq!(targets(&board, "e2"), vec!["e3", "e4", "e5", "e6", "e7", "e8"]);
+}
+
+#[test]
+fn king_does_not_step_into_check() {
+    let board = Board::from_fen("3rk3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(targets(&board, "e1"), vec!["e2", "f1", "f2"]);
+    // nor does it hide behind itself on the line of the checking rook
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/r3K3 w - - 0 1").unwrap();
+    assert_eq!(targets(&board, "e1"), vec!["d2", "e2", "f2"]);
+}
+
+#[test]
+fn legal_moves_answer_the_check() {
+    // block with the knight or the bishop, or move the king out of the bishop's line
+    let board = Board::from_fen("4k3/8/8/8/1b6/8/8/1NB1K3 w - - 0 1").unwrap();
+    let mut moves: Vec<String> = board.legal_moves().iter().map(|mv| mv.to_uci()).collect();
+    moves.sort();
+    assert_eq!(moves, vec!["b1c3", "b1d2", "c1d2", "e1d1", "e1e2", "e1f1", "e1f2"]);
+}
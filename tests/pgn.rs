@@ -0,0 +1,129 @@
+use schach::{read_pgn, Board, Game, GameResult, GameStatus, Move, PgnGame, PgnReader};
+
+const FISCHER_SPASSKY: &str = r#"[Event "F/S Return Match"]
+[Site "Belgrade, Serbia JUG"]
+[Date "1992.11.04"]
+[Round "29"]
+[White "Fischer, Robert J."]
+[Black "Spassky, Boris V."]
+[Result "1/2-1/2"]
+
+1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 {This opening is called the Ruy Lopez.}
+4. Ba4 Nf6 5. O-O Be7 6. Re1 b5 7. Bb3 d6 8. c3 O-O 9. h3 Nb8 10. d4 Nbd7
+11. c4 c6 12. cxb5 axb5 13. Nc3 Bb7 14. Bg5 b4 15. Nb1 h6 16. Bh4 c5 17. dxe5
+Nxe4 18. Bxe7 Qxe7 19. exd6 Qf6 20. Nbd2 Nxd6 21. Nc4 Nxc4 22. Bxc4 Nb6
+23. Ne5 Rae8 24. Bxf7+ Rxf7 25. Nxf7 Rxe1+ 26. Qxe1 Kxf7 27. Qe3 Qg5 28. Qxg5
+hxg5 29. b3 Ke6 30. a3 Kd6 31. axb4 cxb4 32. Ra5 Nd5 33. f3 Bc8 34. Kf2 Bf5
+35. Ra7 g6 36. Ra6+ Kc5 37. Ke1 Nf4 38. g3 Nxh3 39. Kd2 Kb5 40. Rd6 Kc5 41. Ra6
+Nf2 42. g4 Bd3 43. Re6 1/2-1/2
+"#;
+
+#[test]
+fn reads_tags_moves_and_comments() {
+    let games = read_pgn(FISCHER_SPASSKY).unwrap();
+    assert_eq!(games.len(), 1);
+    let game = &games[0];
+    assert_eq!(game.tag("White"), Some("Fischer, Robert J."));
+    assert_eq!(game.tags.len(), 7);
+    assert_eq!(game.result, GameResult::Draw);
+    assert_eq!(game.game.moves().len(), 85);
+    assert_eq!(game.comments, vec![(6, "This opening is called the Ruy Lopez.".to_string())]);
+    assert_eq!(game.game.board().to_fen(), "8/8/4R1p1/2k3p1/1p4P1/1P1b1P2/3K1n2/8 b - - 2 43");
+}
+
+#[test]
+fn variations_nags_escapes_and_multiple_games() {
+    let text = r#"[Event "with \"quotes\""]
+[SetUp "1"]
+[FEN "7k/8/6K1/8/8/8/8/R7 w - - 0 1"]
+
+1. Ra8# $1 (1. Kf6 (1. Kh6) Kg8 {sideline}) ; mate
+1-0
+
+% an escaped line
+1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6?? 4. Qxf7# 1-0
+
+1. d4 *
+"#;
+    let games: Vec<_> = PgnReader::new(text.as_bytes()).collect::<Result<_, _>>().unwrap();
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[0].tag("Event"), Some("with \"quotes\""));
+    assert_eq!(games[0].nags, vec![(1, 1)]);
+    assert_eq!(games[0].comments, vec![(1, "mate".to_string())]);
+    assert_eq!(games[0].game.board().status(), GameStatus::Checkmate(schach::Color::White));
+    let variation = &games[0].variations[0];
+    assert_eq!(variation.ply, 0);
+    assert_eq!(variation.moves.iter().map(Move::to_uci).collect::<Vec<_>>(), vec!["g6f6", "h8g8"]);
+    assert_eq!(variation.comments, vec![(2, "sideline".to_string())]);
+    assert_eq!(variation.variations[0].ply, 0);
+    assert_eq!(variation.variations[0].moves.iter().map(Move::to_uci).collect::<Vec<_>>(), vec!["g6h6"]);
+    assert_eq!(games[1].game.moves().len(), 7);
+    assert_eq!(games[1].result, GameResult::WhiteWins);
+    assert_eq!(games[2].result, GameResult::Unknown);
+}
+
+#[test]
+fn errors_have_line_and_column_and_reading_continues() {
+    let text = "[Event \"broken\"]\n\n1. e4 e5 2. Ke3 Nc6 1-0\n\n[Event \"fine\"]\n1. e4 1-0\n";
+    let results: Vec<_> = PgnReader::new(text.as_bytes()).collect();
+    assert_eq!(results.len(), 2);
+    let error = results[0].as_ref().err().unwrap();
+    assert_eq!((error.line, error.column), (3, 13));
+    assert_eq!(results[1].as_ref().unwrap().tag("Event"), Some("fine"));
+
+    let error = read_pgn("1. e4 (1. d4 e5\n").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 7));
+    let error = read_pgn("[Event \"x]\n1. e4 *").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 1));
+    // variations are replayed from the position before the move they replace
+    let error = read_pgn("1. e4 (1. e5) *").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 11));
+    let error = read_pgn("1. e4 e5 (1... d5 2. d6) *").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 22));
+    let error = read_pgn("(1. e4) 1. d4 *").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 1));
+    let error = read_pgn("1. e4 {open\ncomment").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 7));
+}
+
+#[test]
+fn write_round_trips() {
+    let games = read_pgn(FISCHER_SPASSKY).unwrap();
+    let text = games[0].to_pgn();
+    assert!(text.starts_with("[Event \"F/S Return Match\"]\n[Site \"Belgrade, Serbia JUG\"]\n[Date \"1992.11.04\"]"));
+    assert!(text.contains("\n\n1. e4 e5 2. Nf3 Nc6 3. Bb5 a6 {This opening is called the Ruy Lopez.} 4. Ba4 Nf6"));
+    assert!(text.contains("43. Re6"));
+    assert!(text.trim_end().ends_with("1/2-1/2"));
+    assert!(text.lines().all(|line| line.len() <= 80), "{}", text);
+    assert!(!text.contains("FEN"));
+
+    let again = read_pgn(&text).unwrap();
+    assert_eq!(again[0].game.moves(), games[0].game.moves());
+    assert_eq!(again[0].tags, games[0].tags);
+    assert_eq!(again[0].comments, games[0].comments);
+}
+
+#[test]
+fn write_new_game_from_position() {
+    let mut game = Game::new(Board::from_fen("7k/8/6K1/8/8/8/8/R7 b - - 0 1").unwrap());
+    game.play_san("Kg8").unwrap();
+    game.play_san("Ra8#").unwrap();
+    let pgn = PgnGame::new(game);
+    assert_eq!(
+        pgn.to_pgn(),
+        "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n[White \"?\"]\n[Black \"?\"]\n[Result \"1-0\"]\n\
+         [SetUp \"1\"]\n[FEN \"7k/8/6K1/8/8/8/8/R7 b - - 0 1\"]\n\n1... Kg8 2. Ra8# 1-0\n\n"
+    );
+}
+
+#[test]
+fn write_round_trips_variations() {
+    let text = "1. e4 e5 (1... c5 $2 {Sicilian} (1... e6) 2. Nf3) 2. Nf3 {main} (2. f4 exf4) 2... Nc6 *";
+    let games = read_pgn(text).unwrap();
+    let written = games[0].to_pgn();
+    assert!(written.ends_with("\n\n1. e4 e5 (1... c5 $2 {Sicilian} (1... e6) 2. Nf3) 2. Nf3 {main} (2. f4 exf4)\n2... Nc6 *\n\n"), "{}", written);
+
+    let again = read_pgn(&written).unwrap();
+    assert_eq!(again[0].variations, games[0].variations);
+    assert_eq!(again[0].game.moves(), games[0].game.moves());
+}
@@ -0,0 +1,48 @@
+use schach::{Board, Color, Kind, MoveKind, Piece};
+
+mod common;
+
+use common::square;
+
+#[test]
+fn pawn_on_the_seventh_rank_has_four_promotions() {
+    let board = Board::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let moves = board.get_valid_moves(square("a7"));
+    let mut promotions: Vec<Kind> = moves
+        .iter()
+        .filter_map(|mv| match mv.kind {
+            MoveKind::Promote(kind) => Some(kind),
+            _ => None,
+        })
+        .collect();
+    promotions.sort_by_key(|&kind| kind as usize);
+    assert_eq!(promotions, vec![Kind::Queen, Kind::Rook, Kind::Knight, Kind::Bishop]);
+    let takes = moves.iter().filter(|mv| matches!(mv.kind, MoveKind::PromoteTake(Kind::Rook, _))).count();
+    assert_eq!(takes, 4);
+    assert_eq!(moves.len(), 8);
+}
+
+#[test]
+fn apply_replaces_the_pawn() {
+    let mut board = Board::from_fen("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let knight = board
+        .get_valid_moves(square("a7"))
+        .into_iter()
+        .find(|mv| mv.kind == MoveKind::Promote(Kind::Knight))
+        .unwrap();
+    board.apply(&knight);
+    assert_eq!(board[square("a8")], Some(Piece::new(Color::White, Kind::Knight)));
+    assert_eq!(board[square("a7")], None);
+
+    // black takes back on b8 with a promotion of its own
+    let mut board = Board::from_fen("1R2k3/8/8/8/8/8/p7/1N2K3 b - - 0 1").unwrap();
+    let queen = board
+        .get_valid_moves(square("a2"))
+        .into_iter()
+        .find(|mv| mv.kind == MoveKind::PromoteTake(Kind::Knight, Kind::Queen))
+        .unwrap();
+    board.apply(&queen);
+    assert_eq!(board[square("b1")], Some(Piece::new(Color::Black, Kind::Queen)));
+    assert_eq!(board[square("a2")], None);
+    assert_eq!(board.captures[Color::White][Kind::Knight], 1);
+}
@@ -0,0 +1,79 @@
+use schach::{Board, SanError};
+
+/// the SAN of all legal moves of the position, sorted
+fn sans(fen: &str) -> Vec<String> {
+    let board = Board::from_fen(fen).unwrap();
+    let mut result: Vec<String> = board.legal_moves().iter().map(|mv| board.to_san(mv)).collect();
+    result.sort();
+    result
+}
+
+fn contains(fen: &str, expected: &[&str]) {
+    let sans = sans(fen);
+    for san in expected {
+        assert!(sans.iter().any(|s| s == san), "{} not in {:?}", san, sans);
+    }
+}
+
+#[test]
+fn pieces_pawns_and_captures() {
+    contains("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &["e4", "e3", "Nf3", "Na3"]);
+    contains("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", &["exf6", "e6", "Bb5+", "Qh5+"]);
+}
+
+#[test]
+fn disambiguation() {
+    contains("4k3/8/8/8/R6R/8/8/R3K3 w - - 0 1", &["R1a2", "R4a3", "Rab4", "Rhb4"]);
+    contains("4k3/8/8/8/8/Q1Q5/8/Q3K3 w - - 0 1", &["Q1b2", "Qa3b2", "Qcb2"]);
+    contains("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", &["Nbd2", "Nfd2", "Ne3"]);
+}
+
+#[test]
+fn castling_promotion_and_mate() {
+    contains("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", &["O-O", "O-O-O"]);
+    contains("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1", &["O-O-O+", "b1=Q+", "bxa1=N+"]);
+    contains("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1", &["Ra8#"]);
+}
+
+#[test]
+fn parse_round_trips_every_legal_move() {
+    for fen in &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+        "4k3/8/8/8/8/Q1Q5/8/Q3K3 w - - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+        for mv in board.legal_moves() {
+            assert_eq!(board.parse_san(&board.to_san(&mv)), Ok(mv), "{} in {}", board.to_san(&mv), fen);
+        }
+    }
+}
+
+#[test]
+fn parse_sloppy_input() {
+    let board = Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1").unwrap();
+    assert_eq!(board.parse_san("0-0-0"), board.parse_san("O-O-O+"));
+    assert_eq!(board.parse_san("bxa1q"), board.parse_san("bxa1=Q+"));
+    assert_eq!(board.parse_san("ba1=n"), board.parse_san("bxa1=N"));
+    assert_eq!(board.parse_san("Qxb4"), board.parse_san("Qb4+"));
+    assert!(board.parse_san("Qxb4").is_ok());
+}
+
+#[test]
+fn parse_errors() {
+    let board = Board::from_fen("4k3/8/8/8/8/Q1Q5/8/Q3K3 w - - 0 1").unwrap();
+    match board.parse_san("Qb2") {
+        Err(SanError::Ambiguous(_, mut candidates)) => {
+            candidates.sort();
+            assert_eq!(candidates, vec!["Q1b2", "Qa3b2", "Qcb2"]);
+        }
+        other => panic!("expected an ambiguous move, got {:?}", other),
+    }
+    assert_eq!(board.parse_san("Nf3"), Err(SanError::Illegal("Nf3".to_string())));
+    // an 'x' may be left out, but not added to a move that captures nothing
+    assert_eq!(board.parse_san("Qxe5"), Err(SanError::Illegal("Qxe5".to_string())));
+    assert_eq!(board.parse_san("Qz9"), Err(SanError::Syntax("Qz9".to_string())));
+    assert_eq!(board.parse_san(""), Err(SanError::Syntax("".to_string())));
+    assert!(board.parse_san("b1=Q").is_err());
+}
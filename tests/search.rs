@@ -0,0 +1,134 @@
+use schach::{Board, Bound, DefaultValuation, Entry, TranspositionTable, MATE};
+
+#[test]
+fn finds_mate_in_one() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w - - 0 1").unwrap();
+    let result = board.search(2);
+    assert_eq!(result.best_move.map(|mv| board.to_san(&mv)), Some("Ra8#".to_string()));
+    assert_eq!(result.score, MATE - 1.0);
+}
+
+#[test]
+fn finds_mate_in_two() {
+    // 1. Kc7 Ka7 2. Ra1#
+    let board = Board::from_fen("k7/8/2K5/8/8/8/8/1R6 w - - 0 1").unwrap();
+    assert!(board.search(1).score < MATE - 1.0);
+    let result = board.search(3);
+    assert_eq!(result.score, MATE - 3.0);
+    assert_eq!(result.pv.len(), 3);
+}
+
+#[test]
+fn takes_the_hanging_queen() {
+    let board = Board::from_fen("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1").unwrap();
+    let result = board.search(2);
+    assert_eq!(result.best_move.map(|mv| board.to_san(&mv)), Some("Rxd5".to_string()));
+    assert!(result.score > 0.0);
+}
+
+#[test]
+fn principal_variation_is_legal() {
+    let board = Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+    let result = board.search(3);
+    assert_eq!(result.best_move, result.pv.first().copied());
+    assert!(result.nodes > 0);
+    let mut position = board.clone();
+    for mv in &result.pv {
+        assert!(position.legal_moves().contains(mv), "{} is not legal in {}", mv.to_uci(), position);
+        position.apply(mv);
+    }
+}
+
+#[test]
+fn game_over() {
+    let stalemate = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+    let result = stalemate.search(3);
+    assert_eq!(result.best_move, None);
+    assert_eq!(result.score, 0.0);
+
+    let checkmate = Board::from_fen("R5k1/5ppp/8/8/8/8/8/4K3 b - - 1 1").unwrap();
+    assert_eq!(checkmate.search(3).score, -MATE);
+}
+
+#[test]
+fn drawn_positions_still_have_a_move() {
+    // the fifty-move rule and insufficient material end the search below the root, but not at it
+    for fen in &["4k3/8/8/8/8/8/8/R3K3 w - - 100 80", "4k3/8/8/8/8/8/8/1N2K3 w - - 0 1"] {
+        let board = Board::from_fen(fen).unwrap();
+        let result = board.search(3);
+        let best_move = result.best_move.expect("no move in a drawn position");
+        assert!(board.legal_moves().contains(&best_move), "{:?} in {}", best_move, fen);
+        assert_eq!(result.score, 0.0);
+    }
+
+    let result = Board::default().search(0);
+    assert!(Board::default().legal_moves().contains(&result.best_move.expect("no move at depth 0")));
+}
+
+#[test]
+fn transposition_table_replaces_by_depth() {
+    let mut table = TranspositionTable::new(10 * TranspositionTable::SLOT_SIZE);
+    assert_eq!(table.capacity(), 10);
+    assert_eq!(TranspositionTable::new(0).capacity(), 1);
+    assert!(TranspositionTable::with_megabytes(1).capacity() * TranspositionTable::SLOT_SIZE <= 1 << 20);
+
+    let entry = |key, depth| Entry { key, depth, bound: Bound::Exact, score: 1.0, best_move: None };
+    assert_eq!(table.probe(3), None);
+    table.store(entry(3, 2));
+    assert_eq!(table.probe(3), Some(entry(3, 2)));
+    // 13 takes the same slot as 3
+    assert_eq!(table.probe(13), None);
+    table.store(entry(13, 1));
+    assert_eq!(table.probe(3), Some(entry(3, 2)));
+    table.store(entry(13, 2));
+    assert_eq!(table.probe(13), Some(entry(13, 2)));
+    assert_eq!(table.probe(3), None);
+    table.store(entry(4, 0));
+
+    let stats = table.stats();
+    assert_eq!((stats.probes, stats.hits, stats.filled, stats.capacity), (6, 3, 2, 10));
+    assert_eq!(stats.hit_rate(), 0.5);
+    assert_eq!(stats.fill(), 0.2);
+
+    table.clear();
+    assert_eq!(table.probe(13), None);
+    assert_eq!(table.stats().filled, 0);
+    assert_eq!(table.stats().hit_rate(), 0.0);
+}
+
+#[test]
+fn transposition_table_saves_work() {
+    let positions = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1",
+        "k7/8/2K5/8/8/8/8/1R6 w - - 0 1",
+    ];
+    for fen in positions {
+        let board = Board::from_fen(fen).unwrap();
+        let mut table = TranspositionTable::with_megabytes(1);
+        let plain = board.search(3);
+        let first = board.search_with_table(3, &DefaultValuation(), &mut table);
+        assert_eq!(first.score, plain.score, "{}", fen);
+        assert!(first.nodes <= plain.nodes, "{}", fen);
+
+        // the second search finds the positions of the first
+        let second = board.search_with_table(3, &DefaultValuation(), &mut table);
+        assert_eq!(second.score, plain.score, "{}", fen);
+        assert!(second.nodes < first.nodes, "{}", fen);
+        assert!(table.stats().hits > 0 && table.stats().fill() > 0.0);
+    }
+}
+
+#[test]
+fn transposition_table_keeps_mate_distances() {
+    let board = Board::from_fen("k7/8/2K5/8/8/8/8/1R6 w - - 0 1").unwrap();
+    let mut table = TranspositionTable::with_megabytes(1);
+    board.search_with_table(1, &DefaultValuation(), &mut table);
+    let result = board.search_with_table(3, &DefaultValuation(), &mut table);
+    assert_eq!(result.score, MATE - 3.0);
+    assert_eq!(result.best_move.map(|mv| board.to_san(&mv)), Some("Kc7".to_string()));
+    // the table knows the mate at every ply
+    let mut position = board.clone();
+    position.apply(&result.pv[0]);
+    assert_eq!(position.search_with_table(3, &DefaultValuation(), &mut table).score, -MATE + 2.0);
+}
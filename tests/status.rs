@@ -0,0 +1,82 @@
+use schach::{Board, Color, DrawReason, GameStatus, Kind};
+
+fn status(fen: &str) -> GameStatus {
+    Board::from_fen(fen).unwrap().status()
+}
+
+#[test]
+fn checkmate_and_stalemate() {
+    // fool's mate and a back rank mate
+    assert_eq!(status("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"), GameStatus::Checkmate(Color::Black));
+    assert_eq!(status("R5k1/5ppp/8/8/8/8/8/4K3 b - - 1 1"), GameStatus::Checkmate(Color::White));
+    assert_eq!(status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), GameStatus::Stalemate);
+    assert_eq!(status("K7/8/1q6/8/8/8/8/7k w - - 0 1"), GameStatus::Stalemate);
+    assert_eq!(status("4k3/8/8/8/8/8/8/4K2r w - - 0 1"), GameStatus::Check);
+    assert_eq!(status("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), GameStatus::Ongoing);
+
+    assert!(GameStatus::Checkmate(Color::White).is_over());
+    assert!(GameStatus::Stalemate.is_over());
+    assert!(!GameStatus::Check.is_over());
+    assert_eq!(GameStatus::Checkmate(Color::Black).to_string(), "checkmate, Black wins");
+}
+
+#[test]
+fn move_rules() {
+    assert_eq!(status("4k3/8/8/8/8/8/8/R3K3 w - - 99 80"), GameStatus::Ongoing);
+    assert_eq!(status("4k3/8/8/8/8/8/8/R3K3 w - - 100 80"), GameStatus::Draw(DrawReason::FiftyMoveRule));
+    assert_eq!(status("4k3/8/8/8/8/8/8/R3K3 w - - 150 80"), GameStatus::Draw(DrawReason::SeventyFiveMoveRule));
+    assert!(!DrawReason::FiftyMoveRule.is_automatic());
+    assert!(DrawReason::SeventyFiveMoveRule.is_automatic());
+    assert!(!GameStatus::Draw(DrawReason::FiftyMoveRule).is_over());
+    assert!(GameStatus::Draw(DrawReason::SeventyFiveMoveRule).is_over());
+    // a mate on the 100th halfmove still counts
+    assert_eq!(status("R3k3/8/4K3/8/8/8/8/8 b - - 100 80"), GameStatus::Checkmate(Color::White));
+    // the fifty-move rule has to be claimed, a check is shown until then
+    assert_eq!(status("R3k3/8/8/8/8/8/8/4K3 b - - 100 80"), GameStatus::Check);
+
+    // the 100th halfmove without a capture or pawn move reaches the fifty-move rule
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+    board.apply(&board.parse_san("Ra2").unwrap());
+    assert_eq!(board.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+}
+
+#[test]
+fn halfmove_clock_and_fullmove_number() {
+    let mut board = Board::default();
+    assert_eq!((board.halfmove_clock, board.fullmove_number), (0, 1));
+    let mut play = |san: &str| {
+        let mv = board.parse_san(san).unwrap();
+        board.apply(&mv);
+        (board.halfmove_clock, board.fullmove_number)
+    };
+    assert_eq!(play("Nf3"), (1, 1));
+    assert_eq!(play("Nc6"), (2, 2));
+    // pawn moves and captures reset the clock
+    assert_eq!(play("e4"), (0, 2));
+    assert_eq!(play("Nb4"), (1, 3));
+    assert_eq!(play("Nc3"), (2, 3));
+    assert_eq!(play("Nxa2"), (0, 4));
+}
+
+#[test]
+fn insufficient_material() {
+    let draw = GameStatus::Draw(DrawReason::InsufficientMaterial);
+    assert_eq!(status("4k3/8/8/8/8/8/8/4K3 w - - 0 1"), draw);
+    assert_eq!(status("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"), draw);
+    assert_eq!(status("4k3/8/8/8/8/8/8/4KB2 b - - 0 1"), draw);
+    assert_eq!(status("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"), draw);
+
+    assert_eq!(status("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1"), GameStatus::Ongoing);
+    assert_eq!(status("4kn2/8/8/8/8/8/8/4KN2 w - - 0 1"), GameStatus::Ongoing);
+    assert_eq!(status("4k3/8/8/8/8/8/8/4KP2 w - - 0 1"), GameStatus::Ongoing);
+}
+
+#[test]
+fn remaining_material() {
+    let board = Board::from_fen("r3k3/pp6/8/8/8/8/8/1NB1KQ2 w - - 0 1").unwrap();
+    let white = board.material(Color::White);
+    let black = board.material(Color::Black);
+    assert_eq!((white.minor_pieces(), white.major_pieces(), white[Kind::Pawn]), (2, 1, 0));
+    assert_eq!((black.minor_pieces(), black.major_pieces(), black[Kind::Pawn]), (0, 1, 2));
+    assert_eq!(white.count(&[Kind::King, Kind::Knight]), 2);
+}
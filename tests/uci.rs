@@ -0,0 +1,44 @@
+use schach::{Board, Kind};
+
+#[test]
+fn round_trips_every_legal_move() {
+    for fen in &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1",
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+        for mv in board.legal_moves() {
+            assert_eq!(board.parse_uci_move(&mv.to_uci()), Ok(mv), "{} in {}", mv.to_uci(), fen);
+        }
+    }
+}
+
+#[test]
+fn notation() {
+    let board = Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1").unwrap();
+    assert_eq!(board.parse_uci_move("e8c8").map(|mv| board.to_san(&mv)), Ok("O-O-O+".to_string()));
+    assert_eq!(board.parse_uci_move("b2a1n").map(|mv| board.to_san(&mv)), Ok("bxa1=N+".to_string()));
+    assert!(board.parse_uci_move("b2a1").is_err());
+    assert!(board.parse_uci_move("b2a1k").is_err());
+    assert!(board.parse_uci_move("e8g8").is_err());
+    assert!(board.parse_uci_move("e9e8").is_err());
+    assert!(board.parse_uci_move("e8").is_err());
+}
+
+#[test]
+fn promotion_and_capture_helpers() {
+    let board = Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 b kq - 0 1").unwrap();
+    let mv = |uci: &str| board.parse_uci_move(uci).unwrap();
+    assert_eq!(mv("b2a1n").promotion(), Some(Kind::Knight));
+    assert_eq!(mv("b2a1n").captured(), Some(Kind::Rook));
+    assert_eq!(mv("b2b1q").promotion(), Some(Kind::Queen));
+    assert!(!mv("b2b1q").is_capture());
+    assert_eq!(mv("a3a2").captured(), Some(Kind::Pawn));
+    assert_eq!(mv("b6c5").captured(), None);
+    assert!(mv("d7d5").promotion().is_none());
+
+    let en_passant = Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let take = en_passant.parse_uci_move("e5f6").unwrap();
+    assert_eq!(take.captured(), Some(Kind::Pawn));
+    assert!(take.is_capture());
+}
@@ -0,0 +1,18 @@
+use schach::Board;
+
+mod common;
+
+use common::walk;
+
+#[test]
+fn unapply_restores_position() {
+    for fen in &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ] {
+        let mut board = Board::from_fen(fen).unwrap();
+        // apply and take back every legal move, the position has to be restored exactly each time
+        walk(&mut board, 2, &mut |_| {});
+    }
+}
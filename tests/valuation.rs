@@ -0,0 +1,140 @@
+use schach::{Board, Color, DefaultValuation, Kind, Piece, PieceSquareValuation, SarrattValuation, Square, Valuation};
+
+#[test]
+fn material_balance() {
+    let valuation = DefaultValuation();
+    let board = Board::default();
+    assert_eq!(valuation.material(&board, board.active), 39.0);
+    assert_eq!(valuation.evaluate(&board), 0.0);
+
+    // white is a queen up, which is bad for black to move
+    let board = Board::from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap();
+    assert_eq!(valuation.evaluate(&board), -9.0);
+    assert!((SarrattValuation().evaluate(&board) + 23.75 / 3.0).abs() < 1e-5);
+}
+
+#[test]
+fn phase_from_non_pawn_material() {
+    assert_eq!(Board::default().phase(), 1.0);
+    // a queen less
+    assert_eq!(Board::from_fen("rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1").unwrap().phase(), 20.0 / 24.0);
+    // a rook each
+    assert_eq!(Board::from_fen("r3k3/pppppppp/8/8/8/8/PPPPPPPP/R3K3 w - - 0 1").unwrap().phase(), 4.0 / 24.0);
+    assert_eq!(Board::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap().phase(), 0.0);
+    // more queens than at the start
+    assert_eq!(Board::from_fen("qqqqkqqq/8/8/8/8/8/8/4K3 w - - 0 1").unwrap().phase(), 1.0);
+}
+
+#[test]
+fn values_are_tapered() {
+    let opening = Board::default();
+    let rook_ending = Board::from_fen("r3k3/pppppppp/8/8/8/8/PPPPPPPP/R3K3 w - - 0 1").unwrap();
+    let pawn_ending = Board::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap();
+
+    let sarratt = SarrattValuation();
+    assert_eq!(sarratt.value(&opening, Kind::Pawn), 2.0 / 3.0);
+    assert_eq!(sarratt.value(&pawn_ending, Kind::Pawn), 3.75 / 3.0);
+    let pawn = sarratt.value(&rook_ending, Kind::Pawn);
+    assert!(2.0 / 3.0 < pawn && pawn < 3.75 / 3.0);
+    assert_eq!(sarratt.value(&pawn_ending, Kind::King), 6.5 / 3.0);
+
+    let default = DefaultValuation();
+    assert_eq!(default.value(&opening, Kind::King), f32::MAX);
+    assert_eq!(default.value(&pawn_ending, Kind::King), 4.0);
+    assert_eq!(default.value(&rook_ending, Kind::Rook), 5.0);
+}
+
+/// rooks are worth nothing, everything else counts as usual
+struct NoRooks;
+
+impl Valuation for NoRooks {
+    fn value(&self, board: &Board, kind: Kind) -> f32 {
+        match kind {
+            Kind::Rook => 0.0,
+            kind => DefaultValuation().value(board, kind),
+        }
+    }
+}
+
+/// the player to move wants as many legal moves as possible
+struct Mobility;
+
+impl Valuation for Mobility {
+    fn value(&self, board: &Board, kind: Kind) -> f32 {
+        DefaultValuation().value(board, kind)
+    }
+
+    fn evaluate(&self, board: &Board) -> f32 {
+        board.legal_moves().len() as f32
+    }
+}
+
+#[test]
+fn custom_valuations_drive_the_search() {
+    // the queen can take the rook on a8 or the knight on h8
+    let board = Board::from_fen("r3k2n/8/8/8/8/8/8/Q3K3 w - - 0 1").unwrap();
+    let best = |valuation: &dyn Valuation| board.search_with(1, valuation).best_move.map(|mv| board.to_san(&mv));
+    assert_eq!(best(&DefaultValuation()), Some("Qxa8+".to_string()));
+    assert_eq!(best(&NoRooks), Some("Qxh8+".to_string()));
+    assert_eq!(board.search(1), board.search_with(1, &DefaultValuation()));
+
+    let result = Board::default().search_with(1, &Mobility);
+    assert!(result.best_move.is_some());
+}
+
+#[test]
+fn piece_square_tables() {
+    let tables = PieceSquareValuation::default();
+    let board = Board::default();
+    assert_eq!(tables.evaluate(&board), 0.0);
+    assert_eq!(tables.value(&board, Kind::Knight), 3.2);
+
+    // black reads the tables mirrored
+    let white = Piece::new(Color::White, Kind::Knight);
+    let black = Piece::new(Color::Black, Kind::Knight);
+    assert_eq!(tables.bonus(&board, white, Square::new(6, 7)), -0.4);
+    assert_eq!(tables.bonus(&board, black, Square::new(6, 0)), -0.4);
+    assert!(tables.bonus(&board, white, Square::new(4, 4)) > tables.bonus(&board, white, Square::new(7, 4)));
+
+    // 1. Nf3 develops the knight, black to move is worse off
+    let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1").unwrap();
+    assert!((tables.evaluate(&board) + 0.5).abs() < 1e-5);
+
+    // the king goes to the center only in the endgame
+    let king = Piece::new(Color::White, Kind::King);
+    let (corner, center) = (Square::new(6, 7), Square::new(4, 4));
+    assert!(tables.bonus(&board, king, corner) > tables.bonus(&board, king, center));
+    let ending = Board::from_fen("4k3/pppppppp/8/8/8/8/PPPPPPPP/4K3 w - - 0 1").unwrap();
+    assert!(tables.bonus(&ending, king, corner) < tables.bonus(&ending, king, center));
+}
+
+#[test]
+fn piece_square_tables_from_text() {
+    let text = include_str!("../src/valuation/simplified.txt");
+    assert_eq!(PieceSquareValuation::from_text(text), Ok(PieceSquareValuation::default()));
+    assert_eq!(PieceSquareValuation::load("src/valuation/simplified.txt").unwrap(), PieceSquareValuation::default());
+    assert!(PieceSquareValuation::load("no such file").is_err());
+
+    // a knight worth a whole pawn more on f3
+    let edited = text.replacen("-30   5  10  15  15  10   5 -30", "-30   5  10  15  15 110   5 -30", 1);
+    let edited = PieceSquareValuation::from_text(&edited).unwrap();
+    let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1").unwrap();
+    assert!((edited.evaluate(&board) + 1.5).abs() < 1e-5);
+
+    let error = |text: &str| PieceSquareValuation::from_text(text).unwrap_err();
+    assert_eq!(error("# nothing\nknight middlegame").line, 2);
+    assert_eq!(error("camel middlegame 300").message, "'camel' is not a kind of piece");
+    assert_eq!(error("pawn opening 100").message, "'opening' is neither middlegame nor endgame");
+    assert_eq!(error("pawn endgame 100\n0 0 0 0 0 0 0 0\n0 0 0 0 0 0 0").line, 3);
+    assert_eq!(error("pawn endgame 100\n0 0 0 0 0 0 0 x").message, "'x' is not a number");
+    assert_eq!(error(&text.replace("queen endgame", "queen middlegame")).message, "second table for queen middlegame 900");
+    assert_eq!(error("").message, "no middlegame table for King");
+}
+
+#[test]
+fn piece_square_tables_drive_the_search() {
+    let result = Board::default().search_with(2, &PieceSquareValuation::default());
+    let san = result.best_move.map(|mv| Board::default().to_san(&mv)).unwrap();
+    // a central pawn or a knight towards the center
+    assert!(["e4", "d4", "Nf3", "Nc3"].contains(&san.as_str()), "{}", san);
+}
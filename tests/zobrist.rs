@@ -0,0 +1,54 @@
+use schach::{Board, Color, Kind, Piece, Square};
+
+mod common;
+
+use common::walk;
+
+#[test]
+fn incremental_hash_matches_full_hash() {
+    for fen in &[
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ] {
+        let mut board = Board::from_fen(fen).unwrap();
+        // the incrementally updated hash has to match the one computed from scratch
+        walk(&mut board, 2, &mut |board| {
+            let expected = Board::from_fen(&board.to_fen()).unwrap().zobrist();
+            assert_eq!(board.zobrist(), expected, "{}", board);
+        });
+    }
+}
+
+#[test]
+fn transpositions_have_the_same_hash() {
+    let mut board = Board::default();
+    for (origin, target) in &[("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")] {
+        let origin = Square::from_an(origin).unwrap();
+        let target = Square::from_an(target).unwrap();
+        let mv = board.get_legal_moves(origin).into_iter().find(|mv| mv.target == target).unwrap();
+        board.apply(&mv);
+    }
+    assert_eq!(board.zobrist(), Board::default().zobrist());
+
+    let white = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+    let black = Board::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+    assert_ne!(white.zobrist(), black.zobrist());
+}
+
+#[test]
+fn set_piece_keeps_the_hash() {
+    let mut board = Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let e5 = Square::from_an("e5").unwrap();
+    let e2 = Square::from_an("e2").unwrap();
+    // without the pawn on e5 en passant no longer counts for the hash
+    board.set_piece(e5, None);
+    board.set_piece(e2, Some(Piece::new(Color::White, Kind::Queen)));
+    assert_eq!(board[e2], Some(Piece::new(Color::White, Kind::Queen)));
+    assert_eq!(board.to_fen(), "rnbqkbnr/ppp1p1pp/8/3p1p2/8/8/PPPPQPPP/RNBQKBNR w KQkq f6 0 3");
+    assert_eq!(board.zobrist(), Board::from_fen(&board.to_fen()).unwrap().zobrist());
+    // the bitboards follow, the queen on e2 reaches e7 along the opened e-file
+    assert!(board.get_legal_moves(e2).iter().any(|mv| mv.target == Square::from_an("e7").unwrap()));
+    board.set_piece(Square::from_an("e5").unwrap(), Some(Piece::new(Color::White, Kind::Pawn)));
+    assert_eq!(board.zobrist(), Board::from_fen(&board.to_fen()).unwrap().zobrist());
+}